@@ -0,0 +1,188 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Display;
+use std::{fs, io};
+
+/// Accepted answers for a day, stored in `src/answers/YYYY/NN.txt`:
+///
+/// ```text
+/// Part 1:
+/// 24000
+/// Part 2:
+/// 45000
+/// ```
+///
+/// An answer may span several lines. An empty slot means no answer is recorded yet.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+const PART_ONE_HEADER: &str = "Part 1:";
+const PART_TWO_HEADER: &str = "Part 2:";
+
+impl Answers {
+    pub fn parse(input: &str) -> Self {
+        let mut answers = Answers::default();
+        let mut current: Option<&mut Option<String>> = None;
+
+        for line in input.lines() {
+            match line.trim_end() {
+                PART_ONE_HEADER => current = Some(&mut answers.part_one),
+                PART_TWO_HEADER => current = Some(&mut answers.part_two),
+                _ => {
+                    if let Some(slot) = current.as_deref_mut() {
+                        match slot {
+                            Some(answer) => {
+                                answer.push('\n');
+                                answer.push_str(line);
+                            }
+                            None => *slot = Some(line.to_string()),
+                        }
+                    }
+                }
+            }
+        }
+
+        answers.part_one = answers.part_one.and_then(normalize);
+        answers.part_two = answers.part_two.and_then(normalize);
+        answers
+    }
+
+    /// Reads the answers for `day`. A missing file means nothing is recorded yet.
+    pub fn read(year: i16, day: u8) -> io::Result<Self> {
+        match fs::read_to_string(crate::file_path("answers", year, day)) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        let answer = normalize(answer.to_string());
+        match part {
+            1 => self.part_one = answer,
+            2 => self.part_two = answer,
+            _ => {}
+        }
+    }
+
+    pub fn write(&self, year: i16, day: u8) -> io::Result<()> {
+        fs::create_dir_all(crate::year_path("answers", year))?;
+        fs::write(crate::file_path("answers", year, day), self.to_string())
+    }
+}
+
+fn normalize(answer: String) -> Option<String> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_string())
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{PART_ONE_HEADER}")?;
+        writeln!(f, "{}", self.part_one.as_deref().unwrap_or_default())?;
+        writeln!(f, "{PART_TWO_HEADER}")?;
+        writeln!(f, "{}", self.part_two.as_deref().unwrap_or_default())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing,
+}
+
+/// Compares a solver result against the recorded answer.
+pub fn verify(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match (expected, actual.map(str::trim)) {
+        (None, _) => Verdict::Missing,
+        (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+        (Some(expected), actual) => Verdict::Fail {
+            expected: expected.to_string(),
+            actual: actual.map(str::to_string),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(indoc! {"
+            Part 1:
+            24000
+            Part 2:
+            45000
+        "});
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("45000"));
+    }
+
+    #[test]
+    fn test_parse_empty_and_multiline() {
+        let answers = Answers::parse(indoc! {"
+            Part 1:
+
+            Part 2:
+            ##..
+            #..#
+        "});
+        assert_eq!(answers.part_one, None);
+        assert_eq!(answers.part_two, Some(String::from("##..\n#..#")));
+
+        assert_eq!(Answers::parse(&answers.to_string()), answers);
+        assert_eq!(Answers::parse(""), Answers::default());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut answers = Answers::default();
+        answers.set(2, "45000\n");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("45000"));
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(verify(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(verify(None, Some("42")), Verdict::Missing);
+        assert_eq!(
+            verify(Some("42"), Some("41")),
+            Verdict::Fail {
+                expected: String::from("42"),
+                actual: Some(String::from("41"))
+            }
+        );
+        assert_eq!(
+            verify(Some("42"), None),
+            Verdict::Fail {
+                expected: String::from("42"),
+                actual: None
+            }
+        );
+    }
+}
@@ -0,0 +1,362 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, thread};
+
+use crate::puzzle;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this repository to the Advent of Code servers, as requested by their
+/// maintainer. Set `repository` in `Cargo.toml` to your own repository.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
+/// Successful responses are stored here, so the same resource is never fetched twice.
+pub const CACHE_DIR: &str = ".aoc_cache";
+
+/// Minimum time between two requests, shared by every process using the same cache.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The session cookie from `AOC_SESSION`, or from `~/.adventofcode.session`.
+pub fn session() -> Result<String, String> {
+    if let Some(session) = env::var("AOC_SESSION").ok().and_then(|s| parse_session(&s)) {
+        return Ok(session);
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or_else(|| String::from("could not determine the home directory"))?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    let contents = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "no session found in AOC_SESSION or \"{}\": {e}",
+            path.display()
+        )
+    })?;
+    parse_session(&contents).ok_or_else(|| format!("\"{}\" is empty", path.display()))
+}
+
+fn parse_session(contents: &str) -> Option<String> {
+    let session = contents.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+/// The server to talk to, from `AOC_BASE_URL` or the real one.
+pub fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| String::from(DEFAULT_BASE_URL))
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            cache_dir: PathBuf::from(CACHE_DIR),
+            min_interval: MIN_REQUEST_INTERVAL,
+        }
+    }
+
+    /// A client configured from the environment. `base_url` overrides `AOC_BASE_URL`.
+    pub fn from_env(base_url: Option<&str>) -> Result<Self, String> {
+        let base_url = base_url.map_or_else(self::base_url, String::from);
+        Ok(Client::new(&base_url, &session()?))
+    }
+
+    pub fn with_cache_dir(self, cache_dir: PathBuf) -> Self {
+        Client { cache_dir, ..self }
+    }
+
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        Client {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// The puzzle input of a day, from the cache if it was fetched before.
+    pub fn input(&self, year: i16, day: u8) -> Result<String, String> {
+        self.get_cached(&format!("/{year}/day/{day}/input"), |_| true)
+    }
+
+    /// The puzzle page of a day. A cached page is only reused once it describes both
+    /// parts, so part two shows up after solving part one.
+    pub fn puzzle(&self, year: i16, day: u8) -> Result<String, String> {
+        self.get_cached(&format!("/{year}/day/{day}"), |page| {
+            puzzle::articles(page).len() == 2
+        })
+    }
+
+    /// Submits an answer and returns the response page. Refuses to submit while a
+    /// cooldown set with [`Client::cool_down`] is running.
+    pub fn submit(&self, year: i16, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        if let Some(remaining) = self.cooldown_remaining() {
+            return Err(format!(
+                "The server asked to wait before submitting again, {remaining:.0?} left."
+            ));
+        }
+        self.wait_for_rate_limit();
+
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        println!("Submitting {answer} for part {part} to {url}...");
+        let response = ureq::post(&url)
+            .set("User-Agent", USER_AGENT)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        read_response(&url, response)
+    }
+
+    fn cooldown_file(&self) -> PathBuf {
+        self.cache_dir.join(".cooldown")
+    }
+
+    /// Blocks submissions of every client sharing this cache directory for `duration`.
+    pub fn cool_down(&self, duration: Duration) {
+        let until = SystemTime::now() + duration;
+        let secs = until
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let stored = fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| fs::write(self.cooldown_file(), secs.to_string()));
+        if let Err(e) = stored {
+            eprintln!(
+                "could not record cooldown in \"{}\": {e}",
+                self.cooldown_file().display()
+            );
+        }
+    }
+
+    fn cooldown_remaining(&self) -> Option<Duration> {
+        let secs: u64 = fs::read_to_string(self.cooldown_file())
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        (UNIX_EPOCH + Duration::from_secs(secs))
+            .duration_since(SystemTime::now())
+            .ok()
+    }
+
+    /// Responses of different servers are cached separately, so a local stub server
+    /// does not shadow the real one.
+    fn cache_file(&self, path: &str) -> PathBuf {
+        let server: String = self
+            .base_url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.cache_dir
+            .join(server)
+            .join(path.trim_start_matches('/'))
+    }
+
+    fn get_cached(&self, path: &str, reuse: impl Fn(&str) -> bool) -> Result<String, String> {
+        let cache_file = self.cache_file(path);
+        if let Some(cached) = fs::read_to_string(&cache_file)
+            .ok()
+            .filter(|cached| reuse(cached))
+        {
+            println!("Using cached response from \"{}\".", cache_file.display());
+            return Ok(cached);
+        }
+
+        let body = self.get(path)?;
+        let stored = cache_file
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&cache_file, &body));
+        if let Err(e) = stored {
+            eprintln!(
+                "could not cache response in \"{}\": {e}",
+                cache_file.display()
+            );
+        }
+        Ok(body)
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request of any client
+    /// sharing this cache directory, then marks the start of a new request.
+    fn wait_for_rate_limit(&self) {
+        let stamp = self.cache_dir.join(".last_request");
+        let since_last = fs::metadata(&stamp)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if let Some(wait) = since_last.and_then(|since| self.min_interval.checked_sub(since)) {
+            println!("Waiting {wait:.1?} before the next request...");
+            thread::sleep(wait);
+        }
+
+        let stored = fs::create_dir_all(&self.cache_dir).and_then(|_| fs::write(&stamp, ""));
+        if let Err(e) = stored {
+            eprintln!(
+                "could not record request time in \"{}\": {e}",
+                stamp.display()
+            );
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        self.wait_for_rate_limit();
+
+        let url = format!("{}{path}", self.base_url);
+        println!("Fetching {url}...");
+        let response = ureq::get(&url)
+            .set("User-Agent", USER_AGENT)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        read_response(&url, response)
+    }
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| format!("could not read response from {url}: {e}")),
+        Err(ureq::Error::Status(status, _)) => Err(describe_status(url, status)),
+        Err(e) => Err(format!("request to {url} failed: {e}")),
+    }
+}
+
+fn describe_status(url: &str, status: u16) -> String {
+    match status {
+        400 | 401 => {
+            format!("{url} rejected the session cookie ({status}). It might have expired.")
+        }
+        404 => format!("{url} was not found ({status}). The puzzle might not be unlocked yet."),
+        _ => format!("{url} responded with status {status}."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(parse_session("abc123\n"), Some(String::from("abc123")));
+        assert_eq!(
+            parse_session("session=abc123"),
+            Some(String::from("abc123"))
+        );
+        assert_eq!(parse_session(" \n"), None);
+    }
+
+    #[test]
+    fn test_cache_file() {
+        let client =
+            Client::new("http://127.0.0.1:8080/", "abc").with_cache_dir(PathBuf::from("c"));
+        assert_eq!(
+            client.cache_file("/2022/day/1/input"),
+            PathBuf::from("c/http___127_0_0_1_8080/2022/day/1/input")
+        );
+    }
+
+    /// Serves exactly one request with `body`, so a second request fails to connect.
+    /// The handle returns the request line, the headers and the request body.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let request: Vec<String> = (&mut reader)
+                .lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let length = request
+                .iter()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            (request, String::from_utf8(request_body).unwrap())
+        });
+        (base_url, server)
+    }
+
+    fn header(request: &[String], name: &str) -> Option<String> {
+        request
+            .iter()
+            .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+            .map(String::from)
+    }
+
+    fn test_client(base_url: &str, name: &str) -> Client {
+        let cache_dir = env::temp_dir().join(format!("{name}_{}", std::process::id()));
+        Client::new(base_url, "abc123")
+            .with_cache_dir(cache_dir)
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn test_input_from_stub_server() {
+        let (base_url, server) = serve_once("1000\n2000\n");
+        let client = test_client(&base_url, "aoc_input_test");
+
+        assert_eq!(client.input(2022, 1), Ok(String::from("1000\n2000\n")));
+        let (request, _) = server.join().unwrap();
+        assert_eq!(client.input(2022, 1), Ok(String::from("1000\n2000\n")));
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+
+        assert_eq!(request[0], "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(header(&request, "User-Agent").as_deref(), Some(USER_AGENT));
+        assert_eq!(
+            header(&request, "Cookie").as_deref(),
+            Some("session=abc123")
+        );
+    }
+
+    #[test]
+    fn test_submit_to_stub_server() {
+        let (base_url, server) = serve_once("<article><p>That's the right answer!</p></article>");
+        let client = test_client(&base_url, "aoc_submit_test");
+
+        assert!(client.submit(2022, 1, 2, "45000").is_ok());
+        let (request, body) = server.join().unwrap();
+        assert_eq!(request[0], "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(body, "level=2&answer=45000");
+
+        client.cool_down(Duration::from_secs(60));
+        assert!(client.submit(2022, 1, 2, "45000").is_err());
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::time::{Duration, Instant};
+
+use crate::params::Params;
+use crate::runner::{self, PartResult, Status};
+use crate::solution::DynSolution;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Keep running untimed iterations until this much time has passed (at least one).
+    pub warmup: Duration,
+    /// Keep sampling until this much time has passed, within the iteration limits below.
+    pub measure: Duration,
+    pub min_iterations: usize,
+    pub max_iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: Duration::from_millis(200),
+            measure: Duration::from_secs(2),
+            min_iterations: 5,
+            max_iterations: 1000,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Runs exactly `iterations` timed samples, regardless of how long they take.
+    pub fn with_iterations(self, iterations: usize) -> Self {
+        BenchOptions {
+            measure: Duration::MAX,
+            min_iterations: iterations,
+            max_iterations: iterations,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0_f64
+        };
+
+        Stats {
+            iterations: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Warms up, then collects timing samples from `sample` as configured by `options`.
+pub fn measure(options: &BenchOptions, mut sample: impl FnMut() -> Duration) -> Stats {
+    let warmup = Instant::now();
+    loop {
+        sample();
+        if warmup.elapsed() >= options.warmup {
+            break;
+        }
+    }
+
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    while samples.len() < options.min_iterations
+        || (samples.len() < options.max_iterations && start.elapsed() < options.measure)
+    {
+        samples.push(sample());
+    }
+    Stats::from_samples(&samples)
+}
+
+#[derive(Debug, Clone)]
+pub struct PartBench {
+    pub result: PartResult,
+    /// `None` when the part is not solved or panicked.
+    pub stats: Option<Stats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u8,
+    /// `None` for days whose `parse` does no work.
+    pub parse: Option<Stats>,
+    pub parts: Vec<PartBench>,
+}
+
+impl DayBench {
+    /// Median time of the parse step plus every solved part.
+    pub fn total_median(&self) -> Duration {
+        self.parse
+            .iter()
+            .chain(self.parts.iter().filter_map(|p| p.stats.as_ref()))
+            .map(|s| s.median)
+            .sum()
+    }
+}
+
+/// Benchmarks the parse step and the given parts of a day.
+pub fn bench_day(
+    solution: &dyn DynSolution,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    options: &BenchOptions,
+) -> DayBench {
+    let parse = solution
+        .parses()
+        .then(|| measure(options, || solution.time_parse(input)));
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let result = runner::run_part(solution, part, input, params);
+            let stats = (result.status == Status::Solved)
+                .then(|| measure(options, || solution.time_part(part, input, params)));
+            PartBench { result, stats }
+        })
+        .collect();
+
+    DayBench {
+        day: solution.day(),
+        parse,
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(8), ms(6)]);
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.stddev.as_micros(), 2581);
+
+        let stats = Stats::from_samples(&[ms(3)]);
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_measure_iterations() {
+        let options = BenchOptions {
+            warmup: Duration::ZERO,
+            ..BenchOptions::default()
+        }
+        .with_iterations(7);
+        let mut calls = 0;
+        let stats = measure(&options, || {
+            calls += 1;
+            ms(1)
+        });
+        assert_eq!(stats.iterations, 7);
+        assert_eq!(calls, 8);
+    }
+
+    #[test]
+    fn test_bench_day_skips_identity_parse() {
+        let solution = crate::days::get(2022, 1).unwrap();
+        let input = crate::read_file("examples", 2022, 1).unwrap();
+        let options = BenchOptions {
+            warmup: Duration::ZERO,
+            ..BenchOptions::default()
+        }
+        .with_iterations(1);
+        let day_bench = bench_day(solution, &input, &Params::default(), &[1], &options);
+        assert!(day_bench.parse.is_none());
+        assert_eq!(day_bench.parts[0].stats.unwrap().iterations, 1);
+    }
+}
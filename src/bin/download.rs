@@ -2,104 +2,159 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
+use advent_of_code::aoc::Client;
+use advent_of_code::{checksum, puzzle, YEAR};
+use std::io::{self, Read};
+use std::path::Path;
 use std::{fs, process};
 
 struct Args {
     day: u8,
-    year: Option<i16>,
+    year: i16,
+    force: bool,
+    puzzle: bool,
+    from_file: Option<String>,
+    base_url: Option<String>,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?.unwrap_or(YEAR),
+        force: args.contains("--force"),
+        puzzle: args.contains("--puzzle"),
+        from_file: args.opt_value_from_str("--from-file")?,
+        base_url: args.opt_value_from_str("--base-url")?,
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
     })
 }
 
-fn remove_file(path: &PathBuf) {
-    #[allow(unused_must_use)]
-    {
-        fs::remove_file(path);
+/// Reads an input from `path`, or from stdin if `path` is `-`.
+fn import(path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut contents = String::new();
+        io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| format!("could not read input from stdin: {e}"))?;
+        Ok(contents)
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("could not read \"{path}\": {e}"))
     }
 }
 
-fn exit_with_status(status: i32, path: &PathBuf) -> ! {
-    remove_file(path);
-    process::exit(status);
+/// Reads the file given with `--from-file`, or downloads it with the built-in client.
+fn fetch(args: &Args, download: impl Fn(&Client) -> Result<String, String>) -> String {
+    let contents = match &args.from_file {
+        Some(path) => import(path),
+        None => Client::from_env(args.base_url.as_deref()).and_then(|client| download(&client)),
+    };
+
+    match contents {
+        Ok(contents) if contents.is_empty() => {
+            eprintln!("Refusing to store an empty file.");
+            process::exit(1);
+        }
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
 }
 
-fn main() {
-    // acquire a temp file path to write aoc-cli output to.
-    // aoc-cli expects this file not to be present - delete just in case.
-    let mut tmp_file_path = temp_dir();
-    tmp_file_path.push("aoc_input_tmp");
-    remove_file(&tmp_file_path);
+/// Writes `contents` to `path`, creating the folder of its year if needed.
+fn write_file(path: &str, contents: &str) -> io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Stores the puzzle description as Markdown, and fills in the example input and the
+/// expected example answers if they were not filled in yet.
+fn download_puzzle(args: &Args) {
+    let (year, day_padded) = (args.year, format!("{:02}", args.day));
+    let html = fetch(args, |client| client.puzzle(year, args.day));
+
+    let puzzle_path = format!("src/puzzles/{year}/{day_padded}.md");
+    match write_file(&puzzle_path, &puzzle::to_markdown(&html)) {
+        Ok(_) => println!("Wrote puzzle description to \"{puzzle_path}\""),
+        Err(e) => {
+            eprintln!("Failed to write puzzle description: {e}");
+            process::exit(1);
+        }
+    }
+
+    let example_path = format!("src/examples/{year}/{day_padded}.txt");
+    let existing = fs::read_to_string(&example_path).unwrap_or_default();
+    match puzzle::example(&html) {
+        None => println!("Could not find an example in the puzzle description."),
+        Some(_) if !existing.is_empty() && !args.force => {
+            println!("Kept existing example file \"{example_path}\"")
+        }
+        Some(example) => match write_file(&example_path, &example) {
+            Ok(_) => println!("Wrote example to \"{example_path}\""),
+            Err(e) => eprintln!("Failed to write example file: {e}"),
+        },
+    }
+
+    let module_path = format!("src/days/y{year}/day{day_padded}.rs");
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return;
+    };
+    for (part, answer) in (1..=2).zip(puzzle::example_answers(&html)) {
+        let Some(answer) = answer else {
+            continue;
+        };
+        match puzzle::fill_in_assertion(&module, part, &answer) {
+            Some(updated) => {
+                module = updated;
+                println!("Expecting {answer} for the example of part {part} in \"{module_path}\"");
+            }
+            None => println!(
+                "Example answer of part {part} might be {answer}, left the tests as they are."
+            ),
+        }
+    }
+    if let Err(e) = fs::write(&module_path, module) {
+        eprintln!("Failed to update tests in \"{module_path}\": {e}");
+    }
+}
 
+fn main() {
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
             eprintln!("Failed to process arguments: {e}");
-            exit_with_status(1, &tmp_file_path);
+            process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{day_padded}.txt");
-
-    // check if aoc binary exists and is callable.
-    if Command::new("aoc").arg("-V").output().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        exit_with_status(1, &tmp_file_path);
+    if args.puzzle {
+        download_puzzle(&args);
+        return;
     }
 
-    let mut cmd_args = vec![];
+    let (year, day_padded) = (args.year, format!("{:02}", args.day));
+    let input_path = format!("src/inputs/{year}/{day_padded}.txt");
 
-    if let Some(year) = args.year {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    // scaffold creates empty input files, those are fine to fill in.
+    let existing = fs::read_to_string(&input_path).unwrap_or_default();
+    if !existing.is_empty() && !args.force {
+        eprintln!("Input file \"{input_path}\" already exists. Pass \"--force\" to overwrite it.");
+        process::exit(1);
     }
 
-    cmd_args.append(&mut vec![
-        "--input-file".into(),
-        tmp_file_path.to_string_lossy().to_string(),
-        "--day".into(),
-        args.day.to_string(),
-        "download".into(),
-    ]);
-
-    println!("Downloading input with >aoc {}", cmd_args.join(" "));
-
-    match Command::new("aoc").args(cmd_args).output() {
-        Ok(cmd_output) => {
-            io::stdout()
-                .write_all(&cmd_output.stdout)
-                .expect("could not write cmd stdout to pipe.");
-            io::stderr()
-                .write_all(&cmd_output.stderr)
-                .expect("could not write cmd stderr to pipe.");
-            if !cmd_output.status.success() {
-                exit_with_status(1, &tmp_file_path);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            exit_with_status(1, &tmp_file_path);
-        }
-    }
+    let contents = fetch(&args, |client| client.input(year, args.day));
 
-    match fs::copy(&tmp_file_path, &input_path) {
-        Ok(_) => {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            exit_with_status(0, &tmp_file_path);
-        }
-        Err(e) => {
-            eprintln!("could not copy downloaded input to input file: {e}");
-            exit_with_status(1, &tmp_file_path);
-        }
+    if let Err(e) = write_file(&input_path, &contents) {
+        eprintln!("could not write input file: {e}");
+        process::exit(1);
     }
+    if let Err(e) = checksum::record(year, args.day, &contents) {
+        eprintln!("could not record input checksum: {e}");
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
 }
@@ -2,25 +2,15 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use advent_of_code::answers::Answers;
+use advent_of_code::{selection, YEAR};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::{path::Path, process};
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(_input: &str) -> Option<u32> {
-    None
-}
-
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
-}
-
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+/// Added to every template: registers the parts as a solution and tests them against
+/// the example.
+const SOLUTION_TEMPLATE: &str = r###"crate::solution!(Day{{DAY_PADDED}}, {{DAY}}, {{ANSWER_TYPE}}, {{ANSWER_TYPE}});
 
 #[cfg(test)]
 mod tests {
@@ -28,87 +18,637 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::read_file("examples", {{YEAR}}, {{DAY}}).unwrap();
         assert_eq!(part_one(&input), None);
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::{{PART_TWO_EXAMPLE}}.unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
 "###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+const BLANK_TEMPLATE: &str = r###"pub fn part_one(_input: &str) -> Option<{{ANSWER_TYPE}}> {
+    None
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+pub fn part_two(_input: &str) -> Option<{{ANSWER_TYPE}}> {
+    None
 }
+"###;
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+const LINES_TEMPLATE: &str = r###"fn parse_line(line: &str) -> &str {
+    line
 }
 
-fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
-            process::exit(1);
+fn parse(input: &str) -> Vec<&str> {
+    input.lines().map(parse_line).collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _lines = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _lines = parse(input);
+    None
+}
+"###;
+
+const CHUNKS_TEMPLATE: &str = r###"fn parse(input: &str) -> Vec<Vec<&str>> {
+    input
+        .split("\n\n")
+        .map(|chunk| chunk.lines().collect())
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _chunks = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _chunks = parse(input);
+    None
+}
+"###;
+
+const GRID_TEMPLATE: &str = r###"fn parse(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _grid = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _grid = parse(input);
+    None
+}
+"###;
+
+const REGEX_TEMPLATE: &str = r###"use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref LINE: Regex = Regex::new(r"^(.*)$").unwrap();
+}
+
+/// The captured groups of every line.
+fn parse(input: &str) -> Vec<Vec<&str>> {
+    input
+        .lines()
+        .map(|line| {
+            LINE.captures(line)
+                .unwrap()
+                .iter()
+                .skip(1)
+                .map(|m| m.unwrap().as_str())
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _lines = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{ANSWER_TYPE}}> {
+    let _lines = parse(input);
+    None
+}
+"###;
+
+/// Built-in templates for `--template <name>`. Each one only holds the parsing skeleton
+/// and the parts, `SOLUTION_TEMPLATE` is added to all of them.
+const TEMPLATES: &[(&str, &str)] = &[
+    ("blank", BLANK_TEMPLATE),
+    ("lines", LINES_TEMPLATE),
+    ("chunks", CHUNKS_TEMPLATE),
+    ("grid", GRID_TEMPLATE),
+    ("regex", REGEX_TEMPLATE),
+];
+
+/// Folder of user-defined templates. `templates/<name>.rs` takes precedence over a
+/// built-in template of the same name.
+const USER_TEMPLATE_DIR: &str = "templates";
+
+const YEAR_REGISTRY_TEMPLATE: &str = r###"/*
+ * Registry of the solved days of {{YEAR}}.
+ * `cargo scaffold` adds new days to the `register_days!` list below.
+ */
+register_days! {
+}
+"###;
+
+struct Args {
+    day: u8,
+    year: i16,
+    /// Suffix of a separate example file for part two, e.g. `-2` for `DD-2.txt`.
+    part_two_example: Option<String>,
+    template: String,
+    answer_type: String,
+    /// Overwrite an existing module and example file.
+    force: bool,
+    /// Overwrite an existing input file, which may hold a downloaded input.
+    force_input: bool,
+    dry_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?.unwrap_or(YEAR),
+        part_two_example: args.opt_value_from_str("--part-two-example")?,
+        template: args
+            .opt_value_from_str(["-t", "--template"])?
+            .unwrap_or_else(|| String::from("blank")),
+        answer_type: args
+            .opt_value_from_str("--type")?
+            .unwrap_or_else(|| String::from("u32")),
+        force: args.contains("--force"),
+        force_input: args.contains("--force-input"),
+        dry_run: args.contains("--dry-run"),
+        day: args.free_from_fn(selection::parse_day)?,
+    })
+}
+
+/// Adds `entry` to the list of the registry macro `name` in `registry`, keeping it
+/// sorted. Used for `register_days!` in `src/days/yYYYY/mod.rs` and `register_years!`
+/// in `src/days/mod.rs`. Returns `None` if the entry is already registered.
+fn register(registry: &str, name: &str, entry: &str) -> Result<Option<String>, String> {
+    let entry = format!("    {entry},");
+
+    let mut lines: Vec<&str> = registry.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with(&format!("{name}! {{")))
+        .ok_or_else(|| format!("missing `{name}!`"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "}")
+            .ok_or_else(|| format!("unclosed `{name}!`"))?;
+
+    if lines[start + 1..end].contains(&entry.as_str()) {
+        return Ok(None);
+    }
+    let at = start
+        + 1
+        + lines[start + 1..end]
+            .iter()
+            .take_while(|l| **l < entry.as_str())
+            .count();
+    lines.insert(at, &entry);
+
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+/// Reads the template `name`, either from `templates/<name>.rs` or from the built-ins.
+fn template(name: &str) -> Result<String, String> {
+    let path = Path::new(USER_TEMPLATE_DIR).join(format!("{name}.rs"));
+    match fs::read_to_string(&path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            return Err(format!("Failed to read \"{}\": {e}", path.display()))
         }
+        Err(_) => {}
+    }
+
+    TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| {
+            let names: Vec<&str> = TEMPLATES.iter().map(|(name, _)| *name).collect();
+            format!(
+                "Unknown template \"{name}\", expected one of {} or a file in \"{USER_TEMPLATE_DIR}/\".",
+                names.join(", ")
+            )
+        })
+}
+
+/// Fills in the placeholders of a template and adds the `solution!` call and tests.
+fn render(
+    template: &str,
+    year: i16,
+    day: u8,
+    answer_type: &str,
+    part_two_example: Option<&str>,
+) -> String {
+    let part_two_input = match part_two_example {
+        Some(suffix) => format!("read_file_with_suffix(\"examples\", {year}, {day}, \"{suffix}\")"),
+        None => format!("read_file(\"examples\", {year}, {day})"),
     };
+    format!("{}\n\n{SOLUTION_TEMPLATE}", template.trim_end())
+        .replace("{{PART_TWO_EXAMPLE}}", &part_two_input)
+        .replace("{{ANSWER_TYPE}}", answer_type)
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY_PADDED}}", &format!("{day:02}"))
+        .replace("{{DAY}}", &day.to_string())
+}
+
+/// What to do with a file that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Existing {
+    Fail,
+    Keep,
+    Overwrite,
+}
 
+/// A change made by `scaffold`. All steps are planned up front, so `--dry-run` can list
+/// them and nothing is touched if a file is in the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Write {
+        /// e.g. "empty input file".
+        description: &'static str,
+        path: String,
+        contents: String,
+        existing: Existing,
+    },
+    Register {
+        path: String,
+        name: &'static str,
+        entry: String,
+        /// e.g. "day 7".
+        description: String,
+    },
+}
+
+fn plan(args: &Args, module: String) -> Vec<Step> {
+    let Args { day, year, .. } = *args;
     let day_padded = format!("{day:02}");
+    let replaceable = |force| {
+        if force {
+            Existing::Overwrite
+        } else {
+            Existing::Keep
+        }
+    };
+    let write = |description, path: String, contents: String, existing| Step::Write {
+        description,
+        path,
+        contents,
+        existing,
+    };
 
-    let input_path = format!("src/inputs/{day_padded}.txt");
-    let example_path = format!("src/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
+    let mut steps = vec![];
+    let year_registry = format!("src/days/y{year}/mod.rs");
+    if !Path::new(&year_registry).exists() {
+        steps.push(write(
+            "year registry",
+            year_registry.clone(),
+            YEAR_REGISTRY_TEMPLATE.replace("{{YEAR}}", &year.to_string()),
+            Existing::Fail,
+        ));
+        steps.push(Step::Register {
+            path: String::from("src/days/mod.rs"),
+            name: "register_years",
+            entry: format!("y{year} => {year}"),
+            description: format!("year {year}"),
+        });
+    }
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    steps.push(write(
+        "module file",
+        format!("src/days/y{year}/day{day_padded}.rs"),
+        module,
+        if args.force {
+            Existing::Overwrite
+        } else {
+            Existing::Fail
+        },
+    ));
+    steps.push(Step::Register {
+        path: year_registry,
+        name: "register_days",
+        entry: format!("day{day_padded}::Day{day_padded}"),
+        description: format!("day {day}"),
+    });
+    steps.push(write(
+        "empty input file",
+        format!("src/inputs/{year}/{day_padded}.txt"),
+        String::new(),
+        replaceable(args.force_input),
+    ));
+    steps.push(write(
+        "empty example file",
+        format!("src/examples/{year}/{day_padded}.txt"),
+        String::new(),
+        replaceable(args.force),
+    ));
+    if let Some(suffix) = &args.part_two_example {
+        steps.push(write(
+            "empty example file",
+            format!("src/examples/{year}/{day_padded}{suffix}.txt"),
+            String::new(),
+            replaceable(args.force),
+        ));
+    }
+    // answers are never overwritten, they may hold accepted answers.
+    steps.push(write(
+        "empty answer file",
+        format!("src/answers/{year}/{day_padded}.txt"),
+        Answers::default().to_string(),
+        Existing::Keep,
+    ));
+    steps
+}
+
+/// Files written and replaced so far, so a failed scaffold can be undone.
+#[derive(Default)]
+struct Changes {
+    created: Vec<String>,
+    replaced: Vec<(String, String)>,
+}
+
+impl Changes {
+    /// Creates a file that must not exist yet, failing with `AlreadyExists` otherwise.
+    fn create(&mut self, path: &str, contents: &str) -> Result<(), std::io::Error> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        self.created.push(path.to_string());
+        file.write_all(contents.as_bytes())
+    }
+
+    /// Replaces a file, or creates it if it does not exist.
+    fn replace(&mut self, path: &str, contents: &str) -> Result<(), std::io::Error> {
+        match fs::read_to_string(path) {
+            Ok(previous) => self.replaced.push((path.to_string(), previous)),
+            Err(e) if e.kind() == ErrorKind::NotFound => return self.create(path, contents),
+            Err(e) => return Err(e),
+        }
+        fs::write(path, contents)
+    }
+
+    fn undo(self) {
+        for (path, previous) in self.replaced.into_iter().rev() {
+            match fs::write(&path, previous) {
+                Ok(_) => println!("Restored \"{path}\""),
+                Err(e) => eprintln!("Failed to restore \"{path}\": {e}"),
+            }
+        }
+        for path in self.created.into_iter().rev() {
+            match fs::remove_file(&path) {
+                Ok(_) => println!("Removed \"{path}\""),
+                Err(e) => eprintln!("Failed to remove \"{path}\": {e}"),
+            }
+            // only succeeds for folders that are empty now, e.g. of a new year.
+            if let Some(parent) = Path::new(&path).parent() {
+                let _ = fs::remove_dir(parent);
+            }
         }
+    }
+}
+
+fn describe(step: &Step, dry_run: bool) -> String {
+    let (create, keep, overwrite, register) = if dry_run {
+        (
+            "Would create",
+            "Would keep",
+            "Would overwrite",
+            "Would register",
+        )
+    } else {
+        ("Created", "Kept", "Overwrote", "Registered")
     };
+    match step {
+        Step::Write {
+            description,
+            path,
+            existing,
+            ..
+        } => {
+            let noun = description.trim_start_matches("empty ");
+            match (Path::new(path).exists(), existing) {
+                (false, _) => format!("{create} {description} \"{path}\""),
+                (true, Existing::Overwrite) => format!("{overwrite} {noun} \"{path}\""),
+                (true, _) => format!("{keep} existing {noun} \"{path}\""),
+            }
+        }
+        Step::Register {
+            path, description, ..
+        } => format!("{register} {description} in \"{path}\""),
+    }
+}
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
+fn apply(step: &Step, changes: &mut Changes) -> Result<(), String> {
+    match step {
+        Step::Write {
+            path,
+            contents,
+            existing,
+            ..
+        } => {
+            let written = match existing {
+                Existing::Overwrite => changes.replace(path, contents),
+                _ => changes.create(path, contents),
+            };
+            match written {
+                Err(e) if e.kind() == ErrorKind::AlreadyExists && *existing == Existing::Keep => {
+                    Ok(())
+                }
+                written => written.map_err(|e| format!("Failed to write \"{path}\": {e}")),
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        Step::Register {
+            path, name, entry, ..
+        } => {
+            let registry =
+                fs::read_to_string(path).map_err(|e| format!("Failed to read \"{path}\": {e}"))?;
+            match register(&registry, name, entry)
+                .map_err(|e| format!("Failed to register in \"{path}\": {e}"))?
+            {
+                Some(registry) => changes
+                    .replace(path, &registry)
+                    .map_err(|e| format!("Failed to write \"{path}\": {e}")),
+                None => Ok(()),
+            }
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+            process::exit(1);
         }
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to process arguments: {e}");
             process::exit(1);
         }
-    }
+    };
+    let (day, year) = (args.day, args.year);
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+    let template = match template(&args.template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+    let module = render(
+        &template,
+        year,
+        day,
+        &args.answer_type,
+        args.part_two_example.as_deref(),
+    );
+    let steps = plan(&args, module);
+
+    for step in &steps {
+        if let Step::Write {
+            description,
+            path,
+            existing: Existing::Fail,
+            ..
+        } = step
+        {
+            if Path::new(path).exists() {
+                eprintln!(
+                    "The {description} \"{path}\" already exists. Pass `--force` to overwrite it."
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    if args.dry_run {
+        steps
+            .iter()
+            .for_each(|step| println!("{}", describe(step, true)));
+        return;
+    }
+
+    let mut changes = Changes::default();
+    for step in &steps {
+        let message = describe(step, false);
+        if let Err(e) = apply(step, &mut changes) {
+            eprintln!("{e}");
+            eprintln!("Undoing the changes made so far.");
+            changes.undo();
+            process::exit(1);
+        }
+        println!("{message}");
     }
 
     println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    let year_flag = if year == YEAR {
+        String::new()
+    } else {
+        format!(" --year {year}")
+    };
+    println!("🎄 Type `cargo solve {day:02}{year_flag}` to run your solution.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let module = render(BLANK_TEMPLATE, 2021, 7, "usize", Some("-2"));
+        assert!(module.starts_with("pub fn part_one(_input: &str) -> Option<usize> {"));
+        assert!(module.contains("crate::solution!(Day07, 7, usize, usize);"));
+        assert!(module.contains("crate::read_file(\"examples\", 2021, 7).unwrap();"));
+        assert!(module
+            .contains("crate::read_file_with_suffix(\"examples\", 2021, 7, \"-2\").unwrap();"));
+        assert!(!module.contains("{{"));
+
+        // names in the user's code that merely contain a placeholder are left alone.
+        let module = render("const YEAR_DAYS: u32 = 365;", 2021, 7, "usize", None);
+        assert!(module.starts_with("const YEAR_DAYS: u32 = 365;"));
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = "register_days! {\n    day01::Day01,\n    day09::Day09,\n}\n";
+        assert_eq!(
+            register(registry, "register_days", "day07::Day07"),
+            Ok(Some(String::from(
+                "register_days! {\n    day01::Day01,\n    day07::Day07,\n    day09::Day09,\n}\n"
+            )))
+        );
+        assert_eq!(
+            register(registry, "register_days", "day09::Day09"),
+            Ok(None)
+        );
+        assert!(register(registry, "register_years", "y2021 => 2021").is_err());
+    }
+
+    fn args(force: bool, force_input: bool) -> Args {
+        Args {
+            day: 7,
+            year: 2021,
+            part_two_example: None,
+            template: String::from("blank"),
+            answer_type: String::from("u32"),
+            force,
+            force_input,
+            dry_run: true,
+        }
+    }
+
+    fn existing(steps: &[Step], folder: &str) -> Option<Existing> {
+        steps.iter().find_map(|step| match step {
+            Step::Write { path, existing, .. } if path.starts_with(folder) => Some(*existing),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_plan() {
+        let steps = plan(&args(false, false), String::new());
+        assert_eq!(existing(&steps, "src/days/y2021/day"), Some(Existing::Fail));
+        assert_eq!(existing(&steps, "src/inputs/"), Some(Existing::Keep));
+        assert_eq!(existing(&steps, "src/examples/"), Some(Existing::Keep));
+
+        let steps = plan(&args(true, false), String::new());
+        assert_eq!(
+            existing(&steps, "src/days/y2021/day"),
+            Some(Existing::Overwrite)
+        );
+        assert_eq!(existing(&steps, "src/inputs/"), Some(Existing::Keep));
+        assert_eq!(existing(&steps, "src/examples/"), Some(Existing::Overwrite));
+        assert_eq!(existing(&steps, "src/answers/"), Some(Existing::Keep));
+
+        let steps = plan(&args(false, true), String::new());
+        assert_eq!(existing(&steps, "src/inputs/"), Some(Existing::Overwrite));
+    }
+
+    #[test]
+    fn test_changes() {
+        let dir = std::env::temp_dir().join(format!("scaffold-test-{}", process::id()));
+        let path = dir.join("01.txt").to_string_lossy().to_string();
+        let mut changes = Changes::default();
+
+        changes.create(&path, "input").unwrap();
+        assert_eq!(
+            changes.create(&path, "").unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input");
+        changes.replace(&path, "").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        changes.undo();
+        assert!(!Path::new(&path).exists());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_template() {
+        assert_eq!(template("grid"), Ok(String::from(GRID_TEMPLATE)));
+        assert!(template("unknown").is_err());
+    }
 }
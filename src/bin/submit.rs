@@ -0,0 +1,140 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::answers::Answers;
+use advent_of_code::aoc::Client;
+use advent_of_code::params::Params;
+use advent_of_code::runner::{self, Status};
+use advent_of_code::submission::{self, Attempt, Outcome};
+use advent_of_code::{days, YEAR};
+use std::process;
+
+struct Args {
+    day: u8,
+    part: u8,
+    year: i16,
+    answer: Option<String>,
+    base_url: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?.unwrap_or(YEAR),
+        answer: args.opt_value_from_str("--answer")?,
+        base_url: args.opt_value_from_str("--base-url")?,
+        day: args.free_from_str()?,
+        part: args.free_from_str()?,
+    })
+}
+
+fn exit_with_error(message: impl AsRef<str>) -> ! {
+    eprintln!("{}", message.as_ref());
+    process::exit(1);
+}
+
+/// Runs the solver of a part on the real input, with the default parameters.
+fn solve(year: i16, day: u8, part: u8) -> Result<String, String> {
+    let solution =
+        days::get(year, day).ok_or_else(|| format!("Day {day} of {year} is not registered."))?;
+    let input = advent_of_code::read_input(year, day).map_err(|e| e.to_string())?;
+
+    let params = Params::real(solution.params());
+    let result = runner::run_part(solution, part, &input, &params);
+    match (result.status, result.answer) {
+        (Status::Solved, Some(answer)) => {
+            println!("Part {part} answered {answer} in {:.2?}", result.elapsed);
+            Ok(answer)
+        }
+        (Status::Failed(message), _) => Err(format!("Part {part} failed: {message}")),
+        (Status::Panicked(message), _) => Err(format!("Part {part} panicked: {message}")),
+        _ => Err(format!("Part {part} is not solved.")),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => exit_with_error(format!("Failed to process arguments: {e}")),
+    };
+    let (year, day, part) = (args.year, args.day, args.part);
+    if !(1..=2).contains(&part) {
+        exit_with_error(format!("Invalid part {part}, expected 1 or 2."));
+    }
+
+    let answer = match args
+        .answer
+        .clone()
+        .map_or_else(|| solve(year, day, part), Ok)
+    {
+        Ok(answer) => answer.trim().to_string(),
+        Err(e) => exit_with_error(e),
+    };
+
+    let mut answers = Answers::read(year, day)
+        .unwrap_or_else(|e| exit_with_error(format!("Could not read answer file: {e}")));
+    match answers.get(part) {
+        Some(accepted) if accepted == answer => {
+            println!("{answer} is already the accepted answer of day {day:02} part {part}.");
+            return;
+        }
+        Some(accepted) => exit_with_error(format!(
+            "Day {day:02} part {part} is already solved with {accepted}, not submitting {answer}."
+        )),
+        None => {}
+    }
+
+    let attempts = submission::load_attempts(year)
+        .unwrap_or_else(|e| exit_with_error(format!("Could not read earlier attempts: {e}")));
+    if let Err(e) = submission::guard(&attempts, day, part, &answer) {
+        exit_with_error(format!("Not submitting: {e}"));
+    }
+
+    let client = Client::from_env(args.base_url.as_deref()).unwrap_or_else(|e| exit_with_error(e));
+    let response = client
+        .submit(year, day, part, &answer)
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    match submission::parse_outcome(&response) {
+        Outcome::Correct => {
+            println!("---");
+            println!("🎄 {answer} is the right answer!");
+            answers.set(part, &answer);
+            if let Err(e) = answers.write(year, day) {
+                exit_with_error(format!("Could not record the answer: {e}"));
+            }
+        }
+        Outcome::Rejected {
+            rejection,
+            retry_after,
+        } => {
+            let attempt = Attempt {
+                day,
+                part,
+                rejection,
+                answer: answer.clone(),
+            };
+            if let Err(e) = submission::record_attempt(year, &attempt) {
+                eprintln!("Could not record the attempt: {e}");
+            }
+            if let Some(retry_after) = retry_after {
+                client.cool_down(retry_after);
+            }
+            exit_with_error(format!(
+                "{answer} is not the right answer ({}).",
+                rejection.name().replace('_', " ")
+            ));
+        }
+        Outcome::TooRecent(retry_after) => {
+            client.cool_down(retry_after);
+            exit_with_error(format!(
+                "Submitted too recently, try again in {retry_after:.0?}."
+            ));
+        }
+        Outcome::WrongLevel => exit_with_error(format!(
+            "Part {part} is either already solved or not unlocked yet."
+        )),
+        Outcome::Unknown(text) => exit_with_error(format!("Unexpected response: {text}")),
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::{fs, io};
+
+/// Checksums of the downloaded inputs of a year, one `<day> <hash> <length>` line per day.
+pub fn checksum_path(year: i16) -> PathBuf {
+    crate::year_path("inputs", year).join(".checksums")
+}
+
+/// A 64-bit FNV-1a hash of an input, along with its length in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checksum {
+    pub hash: u64,
+    pub len: usize,
+}
+
+impl Checksum {
+    pub fn of(contents: &str) -> Self {
+        let hash = contents.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        Checksum {
+            hash,
+            len: contents.len(),
+        }
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x} {}", self.hash, self.len)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputState {
+    /// No checksum was recorded for this day.
+    Unknown,
+    Intact,
+    /// The input is shorter than when it was recorded.
+    Truncated,
+    Edited,
+}
+
+pub fn parse(contents: &str) -> BTreeMap<u8, Checksum> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let day = fields.next()?.parse().ok()?;
+            let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+            let len = fields.next()?.parse().ok()?;
+            Some((day, Checksum { hash, len }))
+        })
+        .collect()
+}
+
+fn format(checksums: &BTreeMap<u8, Checksum>) -> String {
+    checksums
+        .iter()
+        .map(|(day, checksum)| format!("{day:02} {checksum}\n"))
+        .collect()
+}
+
+pub fn read(year: i16) -> io::Result<BTreeMap<u8, Checksum>> {
+    match fs::read_to_string(checksum_path(year)) {
+        Ok(contents) => Ok(parse(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Records the checksum of a freshly stored input, replacing any previous one for `day`.
+pub fn record(year: i16, day: u8, contents: &str) -> io::Result<()> {
+    let mut checksums = read(year)?;
+    checksums.insert(day, Checksum::of(contents));
+    fs::write(checksum_path(year), format(&checksums))
+}
+
+pub fn state(recorded: Option<&Checksum>, contents: &str) -> InputState {
+    let Some(recorded) = recorded else {
+        return InputState::Unknown;
+    };
+    let actual = Checksum::of(contents);
+    if actual == *recorded {
+        InputState::Intact
+    } else if actual.len < recorded.len {
+        InputState::Truncated
+    } else {
+        InputState::Edited
+    }
+}
+
+/// Compares an input against its recorded checksum.
+pub fn check(year: i16, day: u8, contents: &str) -> io::Result<InputState> {
+    Ok(state(read(year)?.get(&day), contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(Checksum::of("").hash, 0xcbf29ce484222325);
+        assert_eq!(Checksum::of("a").hash, 0xaf63dc4c8601ec8c);
+        assert_eq!(Checksum::of("foobar").to_string(), "85944171f73967e8 6");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let checksums = parse("01 85944171f73967e8 6\n17 af63dc4c8601ec8c 1\n");
+        assert_eq!(checksums[&1], Checksum::of("foobar"));
+        assert_eq!(checksums[&17], Checksum::of("a"));
+        assert_eq!(
+            format(&checksums),
+            "01 85944171f73967e8 6\n17 af63dc4c8601ec8c 1\n"
+        );
+    }
+
+    #[test]
+    fn test_state() {
+        let recorded = Checksum::of("1000\n2000\n");
+        assert_eq!(state(None, "1000\n"), InputState::Unknown);
+        assert_eq!(state(Some(&recorded), "1000\n2000\n"), InputState::Intact);
+        assert_eq!(state(Some(&recorded), "1000\n"), InputState::Truncated);
+        assert_eq!(state(Some(&recorded), "1000\n2001\n"), InputState::Edited);
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Registry of all solved days, grouped by year.
+ * `cargo scaffold` adds new days to the `register_days!` list in `yYYYY/mod.rs`, and new
+ * years to the `register_years!` list below.
+ */
+use crate::solution::DynSolution;
+
+macro_rules! register_days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        use crate::solution::DynSolution;
+
+        $(pub mod $module;)*
+
+        pub static SOLUTIONS: &[&dyn DynSolution] = &[$(&$module::$solution),*];
+    };
+}
+
+macro_rules! register_years {
+    ($($module:ident => $year:literal),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub static YEARS: &[(i16, &[&dyn DynSolution])] = &[$(($year, $module::SOLUTIONS)),*];
+    };
+}
+
+register_years! {
+    y2022 => 2022,
+}
+
+/// The solved days of `year`, empty if there are none.
+pub fn solutions(year: i16) -> &'static [&'static dyn DynSolution] {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map_or(&[], |(_, solutions)| solutions)
+}
+
+pub fn get(year: i16, day: u8) -> Option<&'static dyn DynSolution> {
+    solutions(year)
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+}
@@ -34,11 +34,7 @@ fn parse_input(input: &str) -> Vec<u32> {
     counts
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day01, 1, u32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -46,13 +42,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = crate::read_file("examples", 2022, 1).unwrap();
         assert_eq!(part_one(&input), Some(24000));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = crate::read_file("examples", 2022, 1).unwrap();
         assert_eq!(part_two(&input), Some(45000));
     }
 }
@@ -162,11 +162,7 @@ impl Round {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 2);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day02, 2, u32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -174,7 +170,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 2);
+        let input = crate::read_file("examples", 2022, 2).unwrap();
         assert_eq!(part_one(&input), Some(15));
     }
 
@@ -205,7 +201,7 @@ mod tests {
 
     #[test]
     fn test_parse_input_part_1() {
-        let input = advent_of_code::read_file("examples", 2);
+        let input = crate::read_file("examples", 2022, 2).unwrap();
         let result: Vec<Round> = parse_input_part_one(&input).collect();
         let expected = vec![
             Round {
@@ -296,7 +292,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 2);
+        let input = crate::read_file("examples", 2022, 2).unwrap();
         assert_eq!(part_two(&input), Some(12));
     }
 }
@@ -70,11 +70,7 @@ fn char_priority(input: &char) -> u32 {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day03, 3, u32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -82,7 +78,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = crate::read_file("examples", 2022, 3).unwrap();
         assert_eq!(part_one(&input), Some(157));
     }
 
@@ -103,9 +99,9 @@ mod tests {
 
     #[test]
     fn test_find_common_char() {
-        assert_eq!(find_common_char(vec!("abc", "aef", "aeg").iter()), 'a');
+        assert_eq!(find_common_char(["abc", "aef", "aeg"].iter()), 'a');
         assert_eq!(
-            find_common_char(vec!("ZseFgD", "kSNfDi", "kSNfDj").iter()),
+            find_common_char(["ZseFgD", "kSNfDi", "kSNfDj"].iter()),
             'D'
         );
     }
@@ -120,7 +116,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = crate::read_file("examples", 2022, 3).unwrap();
         assert_eq!(part_two(&input), Some(70));
     }
 }
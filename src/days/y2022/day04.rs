@@ -45,11 +45,7 @@ fn has_some_overlap(left: &RangeInclusive<u32>, right: &RangeInclusive<u32>) ->
         || right.contains(left.end())
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day04, 4, u32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -57,7 +53,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = crate::read_file("examples", 2022, 4).unwrap();
         assert_eq!(part_one(&input), Some(2));
     }
 
@@ -92,7 +88,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = crate::read_file("examples", 2022, 4).unwrap();
         assert_eq!(part_two(&input), Some(4));
     }
 }
@@ -1,8 +1,6 @@
-#[macro_use]
-extern crate lazy_static;
-
 use std::collections::VecDeque;
 
+use lazy_static::lazy_static;
 use regex::Regex;
 
 pub fn part_one(input: &str) -> Option<String> {
@@ -101,8 +99,7 @@ fn execute_move2(stacks: &mut [Vec<char>], cmd: &Command) {
         items.push(item);
     }
     let to = stacks.get_mut(cmd.to - 1).unwrap();
-    while !items.is_empty() {
-        let item = items.pop().unwrap();
+    while let Some(item) = items.pop() {
         to.push(item);
     }
 }
@@ -118,11 +115,7 @@ pub fn part_two(input: &str) -> Option<String> {
     Some(result)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day05, 5, String, String);
 
 #[cfg(test)]
 mod tests {
@@ -130,13 +123,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = &advent_of_code::read_file("examples", 5);
+        let input = &crate::read_file("examples", 2022, 5).unwrap();
         assert_eq!(part_one(input), Some(String::from("CMZ")));
     }
 
     #[test]
     fn test_parse_initial_stacks() {
-        let input = &advent_of_code::read_file("examples", 5);
+        let input = &crate::read_file("examples", 2022, 5).unwrap();
         let (input, _) = split_input(input);
         assert_eq!(
             parse_initial_stacks(input),
@@ -222,7 +215,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
+        let input = crate::read_file("examples", 2022, 5).unwrap();
         assert_eq!(part_two(&input), Some(String::from("MCD")));
     }
 }
@@ -19,11 +19,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     find_marker(input, 14)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 6);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day06, 6, usize, usize);
 
 #[cfg(test)]
 mod tests {
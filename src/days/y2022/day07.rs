@@ -1,8 +1,6 @@
-#[macro_use]
-extern crate lazy_static;
-
 use std::{collections::VecDeque, fmt::Debug, str::Lines};
 
+use lazy_static::lazy_static;
 use regex::Regex;
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -42,20 +40,19 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 enum FSElementType<'a> {
-    FileType(&'a File),
+    FileType(#[allow(dead_code)] &'a File),
     DirectoryType(&'a Directory),
 }
 
 trait FSElement: Debug {
     fn size(&self) -> u32;
 
-    fn name(&self) -> &String;
-
-    fn element_type(&self) -> FSElementType;
+    fn element_type(&self) -> FSElementType<'_>;
 }
 
 #[derive(Debug)]
 struct Directory {
+    #[allow(dead_code)]
     name: String,
     elements: Vec<Box<dyn FSElement>>,
 }
@@ -118,7 +115,7 @@ impl Directory {
         result
     }
 
-    fn iter(&self) -> DirectoryIter {
+    fn iter(&self) -> DirectoryIter<'_> {
         let mut i = DirectoryIter {
             queue: VecDeque::new(),
         };
@@ -132,11 +129,7 @@ impl FSElement for Directory {
         self.elements.iter().map(|e| e.size()).sum()
     }
 
-    fn name(&self) -> &String {
-        &self.name
-    }
-
-    fn element_type(&self) -> FSElementType {
+    fn element_type(&self) -> FSElementType<'_> {
         FSElementType::DirectoryType(self)
     }
 }
@@ -163,6 +156,7 @@ impl<'a> Iterator for DirectoryIter<'a> {
 
 #[derive(Debug)]
 struct File {
+    #[allow(dead_code)]
     name: String,
     size: u32,
 }
@@ -176,20 +170,12 @@ impl FSElement for File {
         self.size
     }
 
-    fn name(&self) -> &String {
-        &self.name
-    }
-
-    fn element_type(&self) -> FSElementType {
+    fn element_type(&self) -> FSElementType<'_> {
         FSElementType::FileType(self)
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day07, 7, u32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -197,13 +183,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 7);
+        let input = crate::read_file("examples", 2022, 7).unwrap();
         assert_eq!(part_one(&input), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 7);
+        let input = crate::read_file("examples", 2022, 7).unwrap();
         assert_eq!(part_two(&input), Some(24933642));
     }
 }
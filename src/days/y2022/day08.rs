@@ -145,11 +145,7 @@ impl Forest {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day08, 8, usize, u32);
 
 #[cfg(test)]
 mod tests {
@@ -157,13 +153,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = crate::read_file("examples", 2022, 8).unwrap();
         assert_eq!(part_one(&input), Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = crate::read_file("examples", 2022, 8).unwrap();
         assert_eq!(part_two(&input), Some(8));
     }
 }
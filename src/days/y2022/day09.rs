@@ -1,47 +1,48 @@
-use std::{cmp::Ordering, collections::HashSet, iter::repeat};
+use std::{cmp::Ordering, collections::HashSet, iter::repeat_n};
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let directions = parse_input(input);
+use crate::error::{self, Error};
+
+pub fn part_one(input: &str) -> error::Result<usize> {
+    let directions = parse_input(input)?;
     let mut rope = Rope::new(2);
     for dir in directions {
         rope.make_step(dir)
     }
-    let set: HashSet<Point> = HashSet::from_iter(rope.history.into_iter());
-    Some(set.len())
+    let set: HashSet<Point> = HashSet::from_iter(rope.history);
+    Ok(set.len())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let directions = parse_input(input);
+pub fn part_two(input: &str) -> error::Result<usize> {
+    let directions = parse_input(input)?;
     let mut rope = Rope::new(10);
     for dir in directions {
         rope.make_step(dir)
     }
-    let set: HashSet<Point> = HashSet::from_iter(rope.history.into_iter());
-    Some(set.len())
+    let set: HashSet<Point> = HashSet::from_iter(rope.history);
+    Ok(set.len())
 }
 
-fn parse_input(input: &str) -> Vec<Direction> {
-    input
-        .lines()
-        .map(|line| {
-            if let Some((direction, count)) = line.split_once(' ') {
-                let count: usize = count.parse().unwrap();
-                let direction: Direction = match direction {
-                    "R" => Direction::Right,
-                    "L" => Direction::Left,
-                    "U" => Direction::Up,
-                    "D" => Direction::Down,
-                    _ => panic!("something when wrong! don't understand direction {direction}"),
-                };
-                repeat(direction).take(count)
-            } else {
-                panic!("something went wrong! line wouldn't split \"{line}\"")
+fn parse_input(input: &str) -> error::Result<Vec<Direction>> {
+    let moves = error::parse_lines(input, |line| {
+        let (name, count) = line
+            .split_once(' ')
+            .ok_or_else(|| Error::new(format!("expected a direction and a count, got \"{line}\"")))?;
+        let direction = match name {
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            _ => {
+                let message = format!("unknown direction \"{name}\"");
+                return Err(Error::new(message).at_column(1));
             }
-        })
-        .fold(vec![], |mut acc, i| {
-            acc.extend(i);
-            acc
-        })
+        };
+        let count: usize = count
+            .parse()
+            .map_err(|e| Error::from(e).at_column(name.len() + 2))?;
+        Ok(repeat_n(direction, count))
+    })?;
+    Ok(moves.into_iter().flatten().collect())
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -145,30 +146,24 @@ impl Rope {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day09, 9, usize, usize);
 
 #[cfg(test)]
 mod tests {
-    use indoc::indoc;
-
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_one(&input), Some(13));
+        let input = crate::read_file("examples", 2022, 9).unwrap();
+        assert_eq!(part_one(&input), Ok(13));
     }
 
     #[test]
     fn test_parse_input() {
-        let input = advent_of_code::read_file("examples", 9);
+        let input = crate::read_file("examples", 2022, 9).unwrap();
         assert_eq!(
             parse_input(&input),
-            vec![
+            Ok(vec![
                 Direction::Right,
                 Direction::Right,
                 Direction::Right,
@@ -193,10 +188,22 @@ mod tests {
                 Direction::Left,
                 Direction::Right,
                 Direction::Right,
-            ]
+            ])
         )
     }
 
+    #[test]
+    fn test_parse_input_malformed() {
+        assert_eq!(
+            parse_input("R 4\nX 2\n").unwrap_err().to_string(),
+            "line 2, column 1: unknown direction \"X\""
+        );
+        assert_eq!(
+            parse_input("R 4\nU x\n").unwrap_err().to_string(),
+            "line 2, column 3: invalid digit found in string"
+        );
+    }
+
     #[test]
     fn test_rope_new() {
         let rope = Rope::new(2);
@@ -324,19 +331,10 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_two(&input), Some(1));
-
-        let input = indoc! {"
-            R 5
-            U 8
-            L 8
-            D 3
-            R 17
-            D 10
-            L 25
-            U 20
-        "};
-        assert_eq!(part_two(input), Some(36));
+        let input = crate::read_file("examples", 2022, 9).unwrap();
+        assert_eq!(part_two(&input), Ok(1));
+
+        let input = crate::read_file_with_suffix("examples", 2022, 9, "-2").unwrap();
+        assert_eq!(part_two(&input), Ok(36));
     }
 }
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, ops::RangeInclusive};
+use std::{collections::VecDeque, fmt::Display, ops::RangeInclusive};
 
 pub fn part_one(input: &str) -> Option<isize> {
     let cpu = Cpu::new(input);
@@ -17,11 +17,7 @@ pub fn part_two(input: &str) -> Option<String> {
     Some(screen.to_string())
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 10);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day10, 10, isize, String);
 
 #[derive(Debug, PartialEq, Eq)]
 enum Instruction {
@@ -144,15 +140,17 @@ impl Screen {
     }
 }
 
-impl ToString for Screen {
-    fn to_string(&self) -> String {
-        self.0
+impl Display for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rendered = self
+            .0
             .map(|line| {
                 line.map(|b| if b { '#' } else { '.' })
                     .iter()
                     .collect::<String>()
             })
-            .join("\n")
+            .join("\n");
+        write!(f, "{rendered}")
     }
 }
 
@@ -164,7 +162,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = crate::read_file("examples", 2022, 10).unwrap();
         assert_eq!(part_one(&input), Some(13140));
     }
 
@@ -178,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = crate::read_file("examples", 2022, 10).unwrap();
         let expected = indoc! { "
             ##..##..##..##..##..##..##..##..##..##..
             ###...###...###...###...###...###...###.
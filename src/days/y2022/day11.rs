@@ -0,0 +1,275 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{self, Error};
+
+pub fn part_one(input: &str) -> error::Result<Option<usize>> {
+    let monkey_game = MonkeyGame::new(input, true)?;
+    Ok(monkey_game.run_simulation(20))
+}
+
+pub fn part_two(input: &str) -> error::Result<Option<usize>> {
+    let monkey_game = MonkeyGame::new(input, false)?;
+    Ok(monkey_game.run_simulation(10_000))
+}
+
+crate::solution!(Day11, 11, usize, usize);
+
+#[derive(Debug)]
+enum WorryChangeParam {
+    Old,
+    Val(usize),
+}
+
+#[derive(Debug)]
+enum WorryChangeOp {
+    Add(WorryChangeParam),
+    Mult(WorryChangeParam),
+}
+
+struct MonkeyGame {
+    monkeys: BTreeMap<usize, Rc<RefCell<Monkey>>>,
+    reduce_worry: bool,
+    modulo: usize,
+}
+
+impl MonkeyGame {
+    fn new(input: &str, reduce_worry: bool) -> error::Result<MonkeyGame> {
+        let monkeys = MonkeyGame::parse_input(input)?;
+        let modulo = monkeys
+            .values()
+            .map(|f| f.as_ref().borrow().test_mod)
+            .reduce(|lhs, rhs| lhs * rhs);
+
+        Ok(MonkeyGame {
+            monkeys,
+            reduce_worry,
+            modulo: error::require(modulo, "no monkeys in the input")?,
+        })
+    }
+
+    fn parse_input(input: &str) -> error::Result<BTreeMap<usize, Rc<RefCell<Monkey>>>> {
+        lazy_static! {
+            static ref LINE1: Regex = Regex::new(r"^Monkey (\d+):$").unwrap();
+            static ref LINE2: Regex = Regex::new(r"^Starting items: (.*)$").unwrap();
+            static ref LINE3: Regex = Regex::new(r"^Operation: new = old (.) (\w+)$").unwrap();
+            static ref LINE4: Regex = Regex::new(r"^Test: divisible by (\d+)$").unwrap();
+            static ref LINE5: Regex = Regex::new(r"^If true: throw to monkey (\d+)$").unwrap();
+            static ref LINE6: Regex = Regex::new(r"^If false: throw to monkey (\d+)$").unwrap();
+        }
+
+        // every monkey takes six lines, separated by blank lines.
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+        let mut monkeys = BTreeMap::new();
+        while lines.peek().is_some() {
+            // line 1
+            let (n, l1c) = next_captures(&mut lines, &LINE1)?;
+            let id: usize = number(l1c[0], n)?;
+
+            // line 2
+            let (n, l2c) = next_captures(&mut lines, &LINE2)?;
+            let items = l2c[0]
+                .split(", ")
+                .map(|s| number(s, n))
+                .collect::<error::Result<_>>()?;
+
+            // line 3
+            let (n, l3c) = next_captures(&mut lines, &LINE3)?;
+            let worry_val = if l3c[1] == "old" {
+                WorryChangeParam::Old
+            } else {
+                WorryChangeParam::Val(number(l3c[1], n)?)
+            };
+            let worry_change = match l3c[0] {
+                "*" => WorryChangeOp::Mult(worry_val),
+                "+" => WorryChangeOp::Add(worry_val),
+                op => {
+                    let message = format!("unknown operation \"{op}\"");
+                    return Err(Error::new(message).at_line(n));
+                }
+            };
+
+            // line 4
+            let (n, l4c) = next_captures(&mut lines, &LINE4)?;
+            let test_mod = number(l4c[0], n)?;
+
+            // line 5
+            let (n, l5c) = next_captures(&mut lines, &LINE5)?;
+            let test_true = number(l5c[0], n)?;
+
+            // line 6
+            let (n, l6c) = next_captures(&mut lines, &LINE6)?;
+            let test_false = number(l6c[0], n)?;
+
+            let monkey = Monkey {
+                id,
+                inspect_count: 0,
+                items,
+                worry_change,
+                test_mod,
+                true_dest: None,
+                false_dest: None,
+                test_true,
+                test_false,
+            };
+            monkeys.insert(monkey.id, Rc::new(RefCell::new(monkey)));
+        }
+
+        for m in monkeys.values() {
+            let mut m = m.as_ref().borrow_mut();
+            m.true_dest = monkeys.get(&m.test_true).cloned();
+            m.false_dest = monkeys.get(&m.test_false).cloned();
+        }
+
+        Ok(monkeys)
+    }
+
+    fn run_simulation(&self, rounds: usize) -> Option<usize> {
+        for _ in 0..rounds {
+            for m in self.monkeys.values() {
+                while m
+                    .as_ref()
+                    .borrow_mut()
+                    .inspect_item(self.reduce_worry, self.modulo)
+                    .is_some()
+                {}
+            }
+        }
+
+        let mut sorted = self
+            .monkeys
+            .values()
+            .map(|rc| {
+                let y = rc.as_ref().borrow();
+                y
+            })
+            .collect::<Vec<Ref<Monkey>>>();
+        sorted.sort_by_key(|m| std::cmp::Reverse(m.inspect_count));
+
+        let first = sorted[0].inspect_count;
+        let second = sorted[1].inspect_count;
+        Some(first * second)
+    }
+}
+
+#[derive(Debug)]
+struct Monkey {
+    id: usize,
+    inspect_count: usize,
+    items: VecDeque<usize>,
+    worry_change: WorryChangeOp,
+    test_mod: usize,
+    true_dest: Option<Rc<RefCell<Monkey>>>,
+    false_dest: Option<Rc<RefCell<Monkey>>>,
+    test_true: usize,
+    test_false: usize,
+}
+
+impl Monkey {
+    fn inspect_item(&mut self, reduce_worry: bool, modulo: usize) -> Option<bool> {
+        self.items.pop_front().map(|orig_item| {
+            self.inspect_count += 1;
+            let item_after_inspect = match &self.worry_change {
+                WorryChangeOp::Add(v) => match v {
+                    WorryChangeParam::Old => orig_item + orig_item,
+                    WorryChangeParam::Val(v) => orig_item + v,
+                },
+                WorryChangeOp::Mult(v) => match v {
+                    WorryChangeParam::Old => orig_item * orig_item,
+                    WorryChangeParam::Val(val) => orig_item * val,
+                },
+            };
+            let mut item = if reduce_worry {
+                item_after_inspect / 3
+            } else {
+                item_after_inspect
+            };
+            item %= modulo;
+            if item % self.test_mod == 0 {
+                self.true_dest
+                    .as_ref()
+                    .unwrap()
+                    .borrow_mut()
+                    .items
+                    .push_back(item);
+                true
+            } else {
+                self.false_dest
+                    .as_ref()
+                    .unwrap()
+                    .borrow_mut()
+                    .items
+                    .push_back(item);
+                false
+            }
+        })
+    }
+}
+
+/// The groups captured by `re` in the next line, pointing errors at that line.
+fn next_captures<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    re: &Regex,
+) -> error::Result<(usize, Vec<&'a str>)> {
+    let (n, line) = lines.next().ok_or_else(|| {
+        Error::new(format!(
+            "unexpected end of input, expected \"{}\"",
+            re.as_str()
+        ))
+    })?;
+    let captures = re.captures(line).ok_or_else(|| {
+        Error::new(format!("expected \"{}\", got \"{line}\"", re.as_str())).at_line(n)
+    })?;
+    let groups = captures
+        .iter()
+        .skip(1)
+        .map(|m| m.map_or("", |m| m.as_str()))
+        .collect();
+    Ok((n, groups))
+}
+
+fn number(s: &str, line: usize) -> error::Result<usize> {
+    s.parse().map_err(|e| Error::from(e).at_line(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 2022, 11).unwrap();
+        assert_eq!(part_one(&input), Ok(Some(10605)));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 2022, 11).unwrap();
+        assert_eq!(part_two(&input), Ok(Some(2713310158)));
+    }
+
+    #[test]
+    fn test_parse_input_malformed() {
+        let input = crate::read_file("examples", 2022, 11).unwrap();
+        let input = input.replacen("new = old * 19", "new = old / 19", 1);
+        assert_eq!(
+            part_one(&input).unwrap_err().to_string(),
+            "line 3: unknown operation \"/\""
+        );
+        assert_eq!(
+            part_one("Monkey 0:\n  Starting items: 79, 98\n").unwrap_err().to_string(),
+            "unexpected end of input, expected \"^Operation: new = old (.) (\\w+)$\""
+        );
+    }
+}
@@ -77,7 +77,7 @@ fn parse_input(input: &str) -> (Pos, Pos, Vec<Vec<u8>>) {
     (start, goal, chars)
 }
 
-fn construct_edges(chars: &Vec<Vec<u8>>) -> BTreeMap<Pos, Vec<Pos>> {
+fn construct_edges(chars: &[Vec<u8>]) -> BTreeMap<Pos, Vec<Pos>> {
     let mut edges = BTreeMap::new();
     let y_max = chars.len() - 1;
     let x_max = chars.first().unwrap().len() - 1;
@@ -119,11 +119,7 @@ fn find_start_points(chars: &[Vec<u8>]) -> Vec<Pos> {
     result
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 12);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day12, 12, u32, u32);
 
 #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Pos(usize, usize);
@@ -146,13 +142,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 12);
+        let input = crate::read_file("examples", 2022, 12).unwrap();
         assert_eq!(part_one(&input), Some(31));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 12);
+        let input = crate::read_file("examples", 2022, 12).unwrap();
         assert_eq!(part_two(&input), Some(29));
     }
 }
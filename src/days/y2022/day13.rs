@@ -1,48 +1,52 @@
 use std::cmp::Ordering;
 
-use itertools::Itertools;
 use lyn::Scanner;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let chunks = input.split_whitespace().chunks(2);
-    let sum = chunks
-        .into_iter()
-        .enumerate()
-        .filter_map(|(i, mut c)| {
-            let lhs = parse_input_line(c.next().unwrap()).unwrap();
-            let rhs = parse_input_line(c.next().unwrap()).unwrap();
-            match lhs.cmp(&rhs) {
-                Ordering::Less => Some(i + 1),
-                Ordering::Greater => None,
-                Ordering::Equal => panic!("these lines shouldn't be equal"),
+use crate::error::{self, Error};
+
+pub fn part_one(input: &str) -> error::Result<usize> {
+    let packets = parse_packets(input)?;
+    if packets.len() % 2 != 0 {
+        return Err(Error::new("expected the packets to come in pairs"));
+    }
+    let mut sum = 0;
+    for (i, pair) in packets.chunks(2).enumerate() {
+        match pair[0].cmp(&pair[1]) {
+            Ordering::Less => sum += i + 1,
+            Ordering::Greater => {}
+            Ordering::Equal => {
+                return Err(Error::new(format!("the packets of pair {} are equal", i + 1)))
             }
-        })
-        .sum();
-    Some(sum)
+        }
+    }
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let div1 = parse_input_line("[[2]]").unwrap();
-    let div2 = parse_input_line("[[6]]").unwrap();
-    let mut packets = input
-        .split_whitespace()
-        .map(|l| parse_input_line(l).unwrap())
-        .collect_vec();
+pub fn part_two(input: &str) -> error::Result<usize> {
+    let div1 = parse_input_line("[[2]]")?;
+    let div2 = parse_input_line("[[6]]")?;
+    let mut packets = parse_packets(input)?;
     packets.push(div1.clone());
     packets.push(div2.clone());
     packets.sort();
     let idx1 = packets.binary_search(&div1).unwrap() + 1;
     let idx2 = packets.binary_search(&div2).unwrap() + 1;
-    Some(idx1 * idx2)
+    Ok(idx1 * idx2)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 13);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+crate::solution!(Day13, 13, usize, usize);
+
+/// Parses every non-empty line of the input, pointing errors at their line.
+fn parse_packets(input: &str) -> error::Result<Vec<Item>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_input_line(line.trim()).map_err(|e| e.at_line(i + 1)))
+        .collect()
 }
 
-fn parse_input_line(input: &str) -> Result<Item, String> {
+fn parse_input_line(input: &str) -> error::Result<Item> {
     let mut p = Parser::new(input);
     p.parse()
 }
@@ -98,55 +102,48 @@ impl Parser {
         }
     }
 
-    fn parse(&mut self) -> Result<Item, String> {
-        self.parse_list()
+    /// An error pointing at the current position of the scanner.
+    fn error(&self, message: String) -> Error {
+        Error::new(message).at_column(self.scanner.cursor() + 1)
     }
 
-    fn parse_list(&mut self) -> Result<Item, String> {
-        if self.scanner.take(&'[') {
-            let mut items: Vec<Item> = vec![];
-            match self.parse_items(&mut items) {
-                Err(msg) => Err(msg),
-                Ok(()) => {
-                    if self.scanner.take(&']') {
-                        Ok(Item::List(items))
-                    } else {
-                        Err(format!("expected list to end with ']'\n{:?}", self.scanner))
-                    }
-                }
-            }
+    fn parse(&mut self) -> error::Result<Item> {
+        let list = self.parse_list()?;
+        match self.scanner.peek() {
+            None => Ok(list),
+            Some(c) => Err(self.error(format!("unexpected token {c} after the packet"))),
+        }
+    }
+
+    fn parse_list(&mut self) -> error::Result<Item> {
+        if !self.scanner.take(&'[') {
+            return Err(self.error(String::from("expected list to start with '['")));
+        }
+        let mut items: Vec<Item> = vec![];
+        self.parse_items(&mut items)?;
+        if self.scanner.take(&']') {
+            Ok(Item::List(items))
         } else {
-            Err(format!(
-                "expected list to start with '['\n{:?}",
-                self.scanner
-            ))
+            Err(self.error(String::from("expected list to end with ']'")))
         }
     }
 
-    fn parse_items(&mut self, items: &mut Vec<Item>) -> Result<(), String> {
+    fn parse_items(&mut self, items: &mut Vec<Item>) -> error::Result<()> {
         loop {
             match self.scanner.peek() {
-                None => return Err(format!("expected an item\n{:?}", self.scanner)),
-                Some(&'[') => match self.parse_list() {
-                    Ok(list) => items.push(list),
-                    Err(msg) => return Err(msg),
-                },
+                None => return Err(self.error(String::from("expected an item"))),
+                Some(&'[') => items.push(self.parse_list()?),
                 Some(&',') => {
                     self.scanner.pop();
-                    match self.parse_items(items) {
-                        Err(msg) => return Err(msg),
-                        Ok(()) => break,
-                    }
+                    self.parse_items(items)?;
+                    break;
                 }
                 Some(&']') => break,
                 Some(c) => {
-                    if *c >= '0' && *c <= '9' {
-                        match self.parse_number(0) {
-                            Ok(val) => items.push(Item::Num(val)),
-                            Err(msg) => return Err(msg),
-                        }
+                    if c.is_ascii_digit() {
+                        items.push(Item::Num(self.parse_number(0)));
                     } else {
-                        return Err(format!("unexpected token {}\n{:?}", c, self.scanner));
+                        return Err(self.error(format!("unexpected token {c}")));
                     }
                 }
             }
@@ -154,17 +151,11 @@ impl Parser {
         Ok(())
     }
 
-    fn parse_number(&mut self, value: u32) -> Result<u32, String> {
-        let num = self.scanner.transform(|c| {
-            if *c >= '0' && *c <= '9' {
-                Some(c.to_string().parse::<u32>().unwrap())
-            } else {
-                None
-            }
-        });
+    fn parse_number(&mut self, value: u32) -> u32 {
+        let num = self.scanner.transform(|c| c.to_digit(10));
         match num {
             Some(n) => self.parse_number(n + (value * 10)),
-            None => Ok(value),
+            None => value,
         }
     }
 }
@@ -175,14 +166,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_one(&input), Some(13));
+        let input = crate::read_file("examples", 2022, 13).unwrap();
+        assert_eq!(part_one(&input), Ok(13));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 13);
-        assert_eq!(part_two(&input), Some(140));
+        let input = crate::read_file("examples", 2022, 13).unwrap();
+        assert_eq!(part_two(&input), Ok(140));
     }
 
     #[test]
@@ -212,6 +203,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_input_malformed() {
+        assert_eq!(
+            parse_input_line("[1,[2,x]]").unwrap_err().to_string(),
+            "column 7: unexpected token x"
+        );
+        assert_eq!(
+            parse_input_line("[1,2").unwrap_err().to_string(),
+            "column 5: expected an item"
+        );
+        assert_eq!(
+            part_one("[1]\n[2]\n\n[3]\n4]\n").unwrap_err().to_string(),
+            "line 5, column 1: expected list to start with '['"
+        );
+    }
+
     #[test]
     fn test_item_cmp() {
         assert_eq!(Item::Num(2).cmp(&Item::Num(2)), Ordering::Equal);
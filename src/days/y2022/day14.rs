@@ -1,6 +1,6 @@
 use std::{fmt::Display, ops::RangeInclusive};
 
-use advent_of_code::sparse_table::SparseTable;
+use crate::sparse_table::SparseTable;
 use itertools::Itertools;
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -15,16 +15,11 @@ pub fn part_two(input: &str) -> Option<usize> {
     let mut cave = Cave::new(input, true);
 
     while cave.drop_sand().is_some() {}
-    println!("{cave}");
 
     Some(cave.sand_count)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 14);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day14, 14, usize, usize);
 
 struct Cave {
     table: SparseTable<usize, Tile>,
@@ -139,7 +134,7 @@ impl Cave {
             return Err(MoveErr::Abyss);
         }
 
-        match &self.table.get((row, col)) {
+        match *self.table.get((row, col)) {
             Tile::Rock | Tile::Sand => Err(MoveErr::Blocked),
             Tile::Air => Ok((col, row)),
         }
@@ -237,7 +232,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 14);
+        let input = crate::read_file("examples", 2022, 14).unwrap();
         assert_eq!(part_one(&input), Some(24));
     }
 
@@ -387,7 +382,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 14);
+        let input = crate::read_file("examples", 2022, 14).unwrap();
         assert_eq!(part_two(&input), Some(93));
     }
 }
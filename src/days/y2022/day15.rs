@@ -5,20 +5,23 @@ use lazy_static::lazy_static;
 use num_traits::ToPrimitive;
 use regex::Regex;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    not_beacon_count(input, 2_000_000)
-}
+use crate::params::{Param, Params};
+
+const PARAMS: &[Param] = &[
+    Param::new::<isize>("row", "2000000", "10"),
+    Param::new::<isize>("max_coordinate", "4000000", "20"),
+];
 
-pub fn part_two(input: &str) -> Option<isize> {
-    run_part_two(input, (0, 4_000_000))
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    not_beacon_count(input, params.get("row"))
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 15);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+pub fn part_two(input: &str, params: &Params) -> Option<isize> {
+    run_part_two(input, (0, params.get("max_coordinate")))
 }
 
+crate::solution!(Day15, 15, usize, isize, PARAMS);
+
 fn not_beacon_count(input: &str, row: isize) -> Option<usize> {
     let mut beacons_on_row: BTreeSet<isize> = BTreeSet::new();
     let mut sensors_on_row: BTreeSet<isize> = BTreeSet::new();
@@ -132,14 +135,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 15);
-        assert_eq!(not_beacon_count(&input, 10), Some(26));
+        let input = crate::read_file("examples", 2022, 15).unwrap();
+        assert_eq!(part_one(&input, &Params::example(PARAMS)), Some(26));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 15);
-        let result = run_part_two(&input, (0, 20));
-        assert_eq!(result, Some(56000011));
+        let input = crate::read_file("examples", 2022, 15).unwrap();
+        assert_eq!(part_two(&input, &Params::example(PARAMS)), Some(56000011));
     }
 }
@@ -1,51 +1,37 @@
-#[macro_use]
-extern crate lazy_static;
-
 use std::{cell::RefCell, collections::BTreeMap};
 
 use pathfinding::{
     prelude::astar,
     prelude::{dijkstra, dijkstra_all},
 };
+use lazy_static::lazy_static;
 use regex::Regex;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (cave, state) = CaveSystem::parse_input(input);
-    let max_pressure_drop = cave.max_pressure_drop_rate;
-    let (steps, cost) = answer_part_1(cave, state);
-
-    println!("Max Possible Flow Rate {max_pressure_drop}");
-
-    for c in steps.iter() {
-        println!("Minute {}", c.minute);
-        println!("Location {}", c.location);
-        println!("Open Valves: {:?}", c.open_valves);
-        println!("Pressure Release rate: {}", c.current_flow_rate);
-        println!("Pressure Released so far: {}", c.released_pressure);
-        println!("Cost: {}", c.cost);
-        println!();
-    }
-    println!("Total Cost: {cost}");
-    println!(
-        "Max Possible Pressure Release: {}",
-        max_pressure_drop * CaveSystem::NUM_MINUTES
-    );
+use crate::error::{self, parse_lines, require, Error};
+use crate::params::{Param, Params};
+
+const PARAMS: &[Param] = &[Param::new::<usize>("minutes", "30", "30")];
 
-    Some(steps.last().unwrap().released_pressure)
+pub fn part_one(input: &str, params: &Params) -> error::Result<usize> {
+    let (cave, state) = CaveSystem::parse_input(input, params.get("minutes"))?;
+    let (steps, _) = answer_part_1(cave, state);
+
+    Ok(steps.last().unwrap().released_pressure)
 }
 
 fn answer_part_1(cave: CaveSystem, state: CaveState) -> (Vec<CaveState>, usize) {
+    let num_minutes = cave.num_minutes;
     let cave = RefCell::new(cave);
     astar(
         &state,
         |s| cave.borrow_mut().state_successors(s),
         |s| cave.borrow().huristic_cost(s),
-        |s| s.minute >= CaveSystem::NUM_MINUTES,
+        |s| s.minute >= num_minutes,
     )
     .unwrap()
 }
 
-pub fn part_two(_input: &str) -> Option<usize> {
+pub fn part_two(_input: &str, _params: &Params) -> Option<usize> {
     None
 }
 
@@ -78,12 +64,11 @@ struct CaveSystem {
     pressure_dropped_so_far: usize,
     step_number: usize,
     estimated_cost: usize,
+    num_minutes: usize,
 }
 
 impl CaveSystem {
-    const NUM_MINUTES: usize = 30;
-
-    fn parse_input(input: &str) -> (CaveSystem, CaveState) {
+    fn parse_input(input: &str, num_minutes: usize) -> error::Result<(CaveSystem, CaveState)> {
         lazy_static! {
             static ref RE: Regex =
                 Regex::new(r"^Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.*)$")
@@ -94,50 +79,47 @@ impl CaveSystem {
         let mut tunnels: BTreeMap<String, Vec<String>> = BTreeMap::new();
         let mut tunnel_costs = BTreeMap::new();
 
-        input.lines().for_each(|line| {
-            let caps = RE.captures(line).unwrap();
-            let name = caps.get(1).unwrap().as_str().to_owned();
-            let rate: usize = caps.get(2).unwrap().as_str().parse().unwrap();
-            let links = caps.get(3).unwrap().as_str().split(", ");
+        parse_lines(input, |line| {
+            let caps = require(RE.captures(line), "expected a valve")?;
+            let name = caps[1].to_owned();
+            let rate: usize = caps[2].parse()?;
+            let links = caps[3].split(", ");
 
             tunnels.insert(name.to_string(), links.map(|t| t.to_string()).collect());
 
             if rate > 0 {
                 valves.push(Valve { name, rate });
             };
-        });
+            Ok(())
+        })?;
 
         // calculate all path from AA as that is where we start
         let aa = "AA".to_string();
-        valves
-            .iter()
-            .filter_map(|v| {
-                if v.rate > 0 {
-                    Some(v.name.clone())
-                } else {
-                    None
-                }
-            })
-            .for_each(|dest| {
-                let k = (aa.clone(), dest.clone());
-                let (_, cost) = dijkstra(
+        if !tunnels.contains_key(&aa) {
+            return Err(Error::new("there is no valve AA to start from"));
+        }
+        for dest in valves.iter().filter(|v| v.rate > 0).map(|v| v.name.clone()) {
+            let k = (aa.clone(), dest.clone());
+            let (_, cost) = require(
+                dijkstra(
                     &aa,
                     |a| {
                         tunnels
                             .get(a)
-                            .unwrap()
-                            .iter()
+                            .into_iter()
+                            .flatten()
                             .map(|t| (t.to_owned(), 1_usize))
                     },
                     |a| *a == dest,
-                )
-                .unwrap();
-                tunnel_costs.insert(k, cost);
-            });
+                ),
+                format!("valve {dest} cannot be reached from AA"),
+            )?;
+            tunnel_costs.insert(k, cost);
+        }
 
         let unrealized_pressure_drop = valves.iter().map(|v| v.rate).sum();
 
-        (
+        Ok((
             CaveSystem {
                 valves,
                 tunnels,
@@ -148,6 +130,7 @@ impl CaveSystem {
                 pressure_dropped_so_far: 0,
                 step_number: 0,
                 estimated_cost: 0,
+                num_minutes,
             },
             CaveState {
                 current_flow_rate: 0,
@@ -157,7 +140,7 @@ impl CaveSystem {
                 released_pressure: 0,
                 cost: 0,
             },
-        )
+        ))
     }
 
     fn valve_travel_cost(&mut self, start: &str, dest: &str) -> usize {
@@ -190,7 +173,7 @@ impl CaveSystem {
 
     fn state_successors(&mut self, state: &CaveState) -> Vec<(CaveState, usize)> {
         let mut result = vec![];
-        if state.minute < Self::NUM_MINUTES {
+        if state.minute < self.num_minutes {
             for v in self.valves.clone().iter() {
                 if !state.open_valves.contains(&v.name) {
                     let s = self.advance_state(state, v);
@@ -199,7 +182,7 @@ impl CaveSystem {
             }
             if result.is_empty() {
                 let mut new_state = state.clone();
-                new_state.minute = CaveSystem::NUM_MINUTES;
+                new_state.minute = self.num_minutes;
                 new_state.cost = 0;
                 let remaining_minutes = new_state.minute - state.minute;
                 new_state.cost =
@@ -220,8 +203,8 @@ impl CaveSystem {
         new_state.minute = state.minute + valve_cost;
         new_state.location = valve.name.to_owned();
 
-        if new_state.minute >= Self::NUM_MINUTES {
-            new_state.minute = Self::NUM_MINUTES;
+        if new_state.minute >= self.num_minutes {
+            new_state.minute = self.num_minutes;
         } else {
             new_state.open_valves.push(valve.name.to_owned());
             new_state.current_flow_rate = state.current_flow_rate + valve.rate;
@@ -234,16 +217,12 @@ impl CaveSystem {
     }
 
     fn huristic_cost(&self, state: &CaveState) -> usize {
-        let remaining_minutes = Self::NUM_MINUTES - state.minute;
+        let remaining_minutes = self.num_minutes - state.minute;
         (self.max_pressure_drop_rate - state.current_flow_rate) * remaining_minutes / 5
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 16);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day16, 16, usize, usize, PARAMS);
 
 #[cfg(test)]
 mod tests {
@@ -251,13 +230,30 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_one(&input), Some(1651));
+        let input = crate::read_file("examples", 2022, 16).unwrap();
+        assert_eq!(part_one(&input, &Params::example(PARAMS)), Ok(1651));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(part_two(&input), None);
+        let input = crate::read_file("examples", 2022, 16).unwrap();
+        assert_eq!(part_two(&input, &Params::example(PARAMS)), None);
+    }
+
+    #[test]
+    fn test_parse_input_malformed() {
+        let params = Params::example(PARAMS);
+        assert_eq!(
+            part_one("Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate=x; tunnel leads to valve AA\n", &params)
+                .unwrap_err()
+                .to_string(),
+            "line 2: expected a valve"
+        );
+        assert_eq!(
+            part_one("Valve AA has flow rate=0; tunnels lead to valves BB\nValve CC has flow rate=5; tunnel leads to valve BB\n", &params)
+                .unwrap_err()
+                .to_string(),
+            "valve CC cannot be reached from AA"
+        );
     }
 }
@@ -10,11 +10,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(cave.run_rounds(1_000_000_000_000))
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 17);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day17, 17, usize, usize);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tile {
@@ -382,7 +378,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 17);
+        let input = crate::read_file("examples", 2022, 17).unwrap();
         assert_eq!(part_one(&input), Some(3068));
     }
 
@@ -962,7 +958,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 17);
+        let input = crate::read_file("examples", 2022, 17).unwrap();
         assert_eq!(part_two(&input), Some(1514285714288));
     }
 }
@@ -10,24 +10,19 @@ pub fn part_two(_input: &str) -> Option<usize> {
     None
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 18);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day18, 18, usize, usize);
 
 fn count_open_sides(input: &str) -> Option<usize> {
     let cubes = parse_input(input);
     let mut num_sides = cubes.len() * 6;
     let mut set: HashSet<Cube> = HashSet::new();
-    set.extend(cubes.into_iter());
+    set.extend(cubes);
 
     set.iter().for_each(|cube| {
         let adjacent = possible_adjacent_cubes(cube);
         for c in adjacent {
             if set.contains(&c) {
                 num_sides -= 1;
-            } else {
             }
         }
     });
@@ -87,13 +82,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 18);
+        let input = crate::read_file("examples", 2022, 18).unwrap();
         assert_eq!(part_one(&input), Some(64));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 18);
+        let input = crate::read_file("examples", 2022, 18).unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
@@ -77,11 +77,7 @@ impl MixList {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 20);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day20, 20, i64, i64);
 
 #[cfg(test)]
 mod tests {
@@ -89,13 +85,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 20);
+        let input = crate::read_file("examples", 2022, 20).unwrap();
         assert_eq!(part_one(&input), Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 20);
+        let input = crate::read_file("examples", 2022, 20).unwrap();
         assert_eq!(part_two(&input), Some(1623178306));
     }
 }
@@ -1,41 +1,41 @@
-#[macro_use]
-extern crate lazy_static;
-
 use std::collections::HashMap;
 
+use lazy_static::lazy_static;
 use regex::Regex;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut monkeys: Monkeys = parse_input(input);
+use crate::error::{self, parse_lines, require, Error};
+
+pub fn part_one(input: &str) -> error::Result<usize> {
+    let mut monkeys: Monkeys = parse_input(input)?;
     monkeys.find_monkey_value("root")
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut monkeys: Monkeys = parse_input(input);
+pub fn part_two(input: &str) -> error::Result<Option<usize>> {
+    let mut monkeys: Monkeys = parse_input(input)?;
     make_part_two_mods(&mut monkeys);
 
-    let root = monkeys.monkeys.get("root").unwrap();
-    let (lhs_name, rhs_name) = root.params.as_ref().unwrap();
-    let lhs = monkeys.find_monkey_expression(lhs_name);
-    let rhs = monkeys.find_monkey_expression(rhs_name);
+    let root = monkeys.monkey("root")?;
+    let (lhs_name, rhs_name) = require(root.params.as_ref(), "root does not compare two monkeys")?;
+    let lhs = monkeys.find_monkey_expression(lhs_name)?;
+    let rhs = monkeys.find_monkey_expression(rhs_name)?;
 
     if let Expression::Const(val) = lhs {
-        Some(solve_for_variable(val, &rhs))
+        solve_for_variable(val, &rhs).map(Some)
     } else if let Expression::Const(val) = rhs {
-        Some(solve_for_variable(val, &lhs))
+        solve_for_variable(val, &lhs).map(Some)
     } else {
-        None
+        Ok(None)
     }
 }
 
-fn handle_variable(result: usize, expr: &Expression) -> usize {
+fn handle_variable(result: usize, expr: &Expression) -> error::Result<usize> {
     match expr {
-        Expression::Var(_) => result,
+        Expression::Var(_) => Ok(result),
         _ => solve_for_variable(result, expr),
     }
 }
 
-fn solve_for_variable(result: usize, expr: &Expression) -> usize {
+fn solve_for_variable(result: usize, expr: &Expression) -> error::Result<usize> {
     if let Expression::Op(op, lhs, rhs) = expr {
         match (lhs.as_ref(), rhs.as_ref()) {
             (Expression::Const(val), exp) => {
@@ -98,26 +98,24 @@ fn solve_for_variable(result: usize, expr: &Expression) -> usize {
                     }
                 }
             }
-            _ => panic!("something went wrong"),
+            _ => Err(Error::new("humn appears on both sides of an operation")),
         }
     } else {
-        panic!("something went wrong");
+        Err(Error::new("expected an operation involving humn"))
     }
 }
 
-fn parse_input(input: &str) -> Monkeys {
+fn parse_input(input: &str) -> error::Result<Monkeys> {
     lazy_static! {
         static ref CONST_MONKEY: Regex = Regex::new(r"^(\w+): (\d+)$").unwrap();
         static ref EQ_MONKEY: Regex = Regex::new(r"^(\w+): (\w+) (.) (\w+)$").unwrap();
     }
 
-    let monkeys = input
-        .lines()
-        .map(|line| {
-            if let Some(captures) = CONST_MONKEY.captures(line) {
-                let id = captures.get(1).unwrap().as_str();
-                let val: usize = captures.get(2).unwrap().as_str().parse().unwrap();
-                (
+    let monkeys = parse_lines(input, |line| {
+        if let Some(captures) = CONST_MONKEY.captures(line) {
+            let id = &captures[1];
+            let val: usize = captures[2].parse()?;
+            Ok((
                     id.to_owned(),
                     Monkey {
                         variable: None,
@@ -125,29 +123,28 @@ fn parse_input(input: &str) -> Monkeys {
                         operation: None,
                         params: None,
                     },
-                )
-            } else {
-                let captures = EQ_MONKEY.captures(line).unwrap();
-                let id = captures.get(1).unwrap().as_str();
-                let lhs = captures.get(2).unwrap().as_str();
-                let op = captures.get(3).unwrap().as_str();
-                let rhs = captures.get(4).unwrap().as_str();
-                (
-                    id.to_owned(),
+            ))
+        } else {
+            let captures = require(EQ_MONKEY.captures(line), "expected a number or an operation")?;
+            let lhs = &captures[2];
+            let rhs = &captures[4];
+            Ok((
+                    captures[1].to_owned(),
                     Monkey {
                         variable: None,
                         constant: None,
-                        operation: Some(Operation::parse(op)),
+                        operation: Some(Operation::parse(&captures[3])?),
                         params: Some((lhs.to_owned(), rhs.to_owned())),
                     },
-                )
-            }
-        })
-        .collect();
-    Monkeys {
+                ))
+        }
+    })?
+    .into_iter()
+    .collect();
+    Ok(Monkeys {
         monkeys,
         values: HashMap::new(),
-    }
+    })
 }
 
 fn make_part_two_mods(monkeys: &mut Monkeys) {
@@ -165,25 +162,29 @@ struct Monkeys {
 }
 
 impl Monkeys {
-    fn find_monkey_value(&mut self, name: &str) -> Option<usize> {
+    fn monkey(&self, name: &str) -> error::Result<&Monkey> {
+        require(self.monkeys.get(name), format!("unknown monkey \"{name}\""))
+    }
+
+    fn find_monkey_value(&mut self, name: &str) -> error::Result<usize> {
         {
             if let Some(v) = self.values.get(name) {
-                return Some(*v);
+                return Ok(*v);
             }
         }
         let params: (String, String);
         let op: Operation;
         {
-            let monkey = self.monkeys.get(name).unwrap();
+            let monkey = self.monkey(name)?;
             if let Some(value) = monkey.constant {
                 self.values.insert(name.to_owned(), value);
-                Some(value)
+                Ok(value)
             } else {
                 params = monkey.params.as_ref().unwrap().clone();
                 op = monkey.operation.unwrap();
                 let (lhs_name, rhs_name) = params;
-                let lhs = self.find_monkey_value(&lhs_name).unwrap();
-                let rhs = self.find_monkey_value(&rhs_name).unwrap();
+                let lhs = self.find_monkey_value(&lhs_name)?;
+                let rhs = self.find_monkey_value(&rhs_name)?;
                 let result = match op {
                     Operation::Add => lhs + rhs,
                     Operation::Sub => lhs - rhs,
@@ -191,24 +192,24 @@ impl Monkeys {
                     Operation::Div => lhs / rhs,
                 };
                 self.values.insert(name.to_owned(), result);
-                Some(result)
+                Ok(result)
             }
         }
     }
 
-    fn find_monkey_expression(&self, name: &str) -> Expression {
-        let monkey = self.monkeys.get(name).unwrap();
+    fn find_monkey_expression(&self, name: &str) -> error::Result<Expression> {
+        let monkey = self.monkey(name)?;
         if let Some(value) = monkey.constant {
-            return Expression::Const(value);
+            return Ok(Expression::Const(value));
         }
         if let Some(var) = monkey.variable.as_ref() {
-            return Expression::Var(var.clone());
+            return Ok(Expression::Var(var.clone()));
         }
         let (lhs_name, rhs_name) = monkey.params.as_ref().unwrap();
-        let lhs = self.find_monkey_expression(lhs_name);
-        let rhs = self.find_monkey_expression(rhs_name);
+        let lhs = self.find_monkey_expression(lhs_name)?;
+        let rhs = self.find_monkey_expression(rhs_name)?;
         let op = monkey.operation.unwrap();
-        if let (Expression::Const(l_val), Expression::Const(r_val)) = (lhs.clone(), rhs.clone()) {
+        Ok(if let (Expression::Const(l_val), Expression::Const(r_val)) = (lhs.clone(), rhs.clone()) {
             match op {
                 Operation::Add => Expression::Const(l_val + r_val),
                 Operation::Sub => Expression::Const(l_val - r_val),
@@ -217,7 +218,7 @@ impl Monkeys {
             }
         } else {
             Expression::Op(op, Box::new(lhs), Box::new(rhs))
-        }
+        })
     }
 }
 
@@ -237,13 +238,13 @@ enum Operation {
 }
 
 impl Operation {
-    fn parse(input: &str) -> Operation {
+    fn parse(input: &str) -> error::Result<Operation> {
         match input {
-            "+" => Operation::Add,
-            "-" => Operation::Sub,
-            "*" => Operation::Mul,
-            "/" => Operation::Div,
-            _ => panic!("unrecognized operation {input}"),
+            "+" => Ok(Operation::Add),
+            "-" => Ok(Operation::Sub),
+            "*" => Ok(Operation::Mul),
+            "/" => Ok(Operation::Div),
+            _ => Err(Error::new(format!("unrecognized operation {input}"))),
         }
     }
 }
@@ -252,14 +253,10 @@ impl Operation {
 enum Expression {
     Const(usize),
     Op(Operation, Box<Expression>, Box<Expression>),
-    Var(String),
+    Var(#[allow(dead_code)] String),
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 21);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day21, 21, usize, usize);
 
 #[cfg(test)]
 mod tests {
@@ -267,13 +264,31 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 21);
-        assert_eq!(part_one(&input), Some(152));
+        let input = crate::read_file("examples", 2022, 21).unwrap();
+        assert_eq!(part_one(&input), Ok(152));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 21);
-        assert_eq!(part_two(&input), Some(301));
+        let input = crate::read_file("examples", 2022, 21).unwrap();
+        assert_eq!(part_two(&input), Ok(Some(301)));
+    }
+
+    #[test]
+    fn test_parse_input_malformed() {
+        assert_eq!(
+            part_one("root: abcd % efgh\n").unwrap_err().to_string(),
+            "line 1: unrecognized operation %"
+        );
+        assert_eq!(
+            part_one("root: abcd + efgh\nabcd: 1\nefgh: two\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3: expected a number or an operation"
+        );
+        assert_eq!(
+            part_one("root: abcd + efgh\nabcd: 1\n").unwrap_err().to_string(),
+            "unknown monkey \"efgh\""
+        );
     }
 }
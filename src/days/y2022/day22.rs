@@ -186,11 +186,7 @@ impl Direction {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 22);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day22, 22, i32, u32);
 
 #[cfg(test)]
 mod tests {
@@ -198,13 +194,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 22);
+        let input = crate::read_file("examples", 2022, 22).unwrap();
         assert_eq!(part_one(&input), Some(6032));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 22);
+        let input = crate::read_file("examples", 2022, 22).unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
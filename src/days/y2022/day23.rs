@@ -176,11 +176,7 @@ impl Crater {
     }
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 23);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day23, 23, usize, usize);
 
 #[cfg(test)]
 mod tests {
@@ -188,13 +184,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 23);
+        let input = crate::read_file("examples", 2022, 23).unwrap();
         assert_eq!(part_one(&input), Some(110));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 23);
+        let input = crate::read_file("examples", 2022, 23).unwrap();
         assert_eq!(part_two(&input), Some(20));
     }
 }
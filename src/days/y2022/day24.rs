@@ -32,11 +32,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(c1 + c2 + c3)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 24);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day24, 24, usize, usize);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Dir {
@@ -314,13 +310,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 24);
+        let input = crate::read_file("examples", 2022, 24).unwrap();
         assert_eq!(part_one(&input), Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 24);
+        let input = crate::read_file("examples", 2022, 24).unwrap();
         assert_eq!(part_two(&input), Some(54));
     }
 }
@@ -70,11 +70,7 @@ fn snafu_len(input: i64) -> u32 {
     snafu_len_impl(input).0
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 25);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
+crate::solution!(Day25, 25, String, u32);
 
 #[cfg(test)]
 mod tests {
@@ -82,7 +78,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 25);
+        let input = crate::read_file("examples", 2022, 25).unwrap();
         assert_eq!(part_one(&input), Some("2=-1=0".to_string()));
     }
 
@@ -147,7 +143,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 25);
+        let input = crate::read_file("examples", 2022, 25).unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
@@ -0,0 +1,169 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::{self, Display};
+use std::io;
+use std::num::{ParseIntError, TryFromIntError};
+
+/// An error of a solver or helper, usually malformed input, optionally pointing at the
+/// offending line and column of the input (both 1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Points the error at `line`, unless it already points at one.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Points the error at `column`, unless it already points at one.
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column.get_or_insert(column);
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            (None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::new(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::new(message)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::new(e.to_string())
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::new(e.to_string())
+    }
+}
+
+impl From<TryFromIntError> for Error {
+    fn from(e: TryFromIntError) -> Self {
+        Error::new(e.to_string())
+    }
+}
+
+/// Parses every line of `input` with `parse`, pointing errors at the line they occurred on.
+pub fn parse_lines<T>(input: &str, mut parse: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/// Fails with `message` if `value` is `None`, for captures and other lookups that only
+/// fail on malformed input.
+pub fn require<T>(value: Option<T>, message: impl Into<String>) -> Result<T> {
+    value.ok_or_else(|| Error::new(message))
+}
+
+/// The result of a part: `Option<T>` for parts that cannot fail, and `Result<T, E>` or
+/// `Result<Option<T>, E>` for parts that report malformed input as an error.
+pub trait IntoAnswer<T> {
+    fn into_answer(self) -> Result<Option<T>>;
+}
+
+impl<T> IntoAnswer<T> for Option<T> {
+    fn into_answer(self) -> Result<Option<T>> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Into<Error>> IntoAnswer<T> for std::result::Result<T, E> {
+    fn into_answer(self) -> Result<Option<T>> {
+        self.map(Some).map_err(Into::into)
+    }
+}
+
+impl<T, E: Into<Error>> IntoAnswer<T> for std::result::Result<Option<T>, E> {
+    fn into_answer(self) -> Result<Option<T>> {
+        self.map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Error::new("oops").to_string(), "oops");
+        assert_eq!(Error::new("oops").at_line(3).to_string(), "line 3: oops");
+        assert_eq!(
+            Error::new("oops")
+                .at_column(7)
+                .at_line(3)
+                .at_line(4)
+                .to_string(),
+            "line 3, column 7: oops"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse = |line: &str| line.parse::<u32>().map_err(Error::from);
+        assert_eq!(parse_lines("1\n2\n", parse), Ok(vec![1, 2]));
+        assert_eq!(
+            parse_lines("1\nx\n", parse).unwrap_err().to_string(),
+            "line 2: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_into_answer() {
+        assert_eq!(IntoAnswer::<u32>::into_answer(Some(1)), Ok(Some(1)));
+        assert_eq!(
+            IntoAnswer::<u32>::into_answer(Ok::<u32, String>(2)),
+            Ok(Some(2))
+        );
+        assert_eq!(
+            IntoAnswer::<u32>::into_answer(Ok::<Option<u32>, String>(None)),
+            Ok(None)
+        );
+        assert_eq!(
+            IntoAnswer::<u32>::into_answer(Err::<u32, _>("bad")),
+            Err(Error::new("bad"))
+        );
+    }
+}
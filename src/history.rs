@@ -0,0 +1,291 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::bench::{DayBench, Stats};
+
+/// Benchmark results of every `--bench` run are appended to this file.
+pub const HISTORY_FILE: &str = "bench_history.csv";
+
+const HEADER: &str = "key,year,day,part,min_ns,median_ns,mean_ns,stddev_ns,iterations";
+
+/// One benchmarked step of a day. `part` 0 is the parse step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub key: String,
+    pub year: i16,
+    pub day: u8,
+    pub part: u8,
+    pub stats: Stats,
+}
+
+/// Identifies the current run: the short git commit hash (suffixed with `-dirty` when
+/// there are uncommitted changes), or the unix timestamp outside of a git checkout.
+pub fn current_key() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if !hash.is_empty() => match git(&["status", "--porcelain"]) {
+            Some(status) if !status.is_empty() => format!("{hash}-dirty"),
+            _ => hash,
+        },
+        _ => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+pub fn records(key: &str, year: i16, day_bench: &DayBench) -> Vec<Record> {
+    let parse = day_bench.parse.map(|stats| Record {
+        key: key.to_string(),
+        year,
+        day: day_bench.day,
+        part: 0,
+        stats,
+    });
+    let parts = day_bench.parts.iter().filter_map(|part| {
+        part.stats.map(|stats| Record {
+            key: key.to_string(),
+            year,
+            day: day_bench.day,
+            part: part.result.part,
+            stats,
+        })
+    });
+    parse.into_iter().chain(parts).collect()
+}
+
+fn format_record(r: &Record) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{}",
+        r.key,
+        r.year,
+        r.day,
+        r.part,
+        r.stats.min.as_nanos(),
+        r.stats.median.as_nanos(),
+        r.stats.mean.as_nanos(),
+        r.stats.stddev.as_nanos(),
+        r.stats.iterations
+    )
+}
+
+fn parse_record(line: &str) -> Option<Record> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let [key, year, day, part, min, median, mean, stddev, iterations] = fields[..] else {
+        return None;
+    };
+    let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+
+    Some(Record {
+        key: key.to_string(),
+        year: year.parse().ok()?,
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        stats: Stats {
+            iterations: iterations.parse().ok()?,
+            min: nanos(min)?,
+            median: nanos(median)?,
+            mean: nanos(mean)?,
+            stddev: nanos(stddev)?,
+        },
+    })
+}
+
+pub fn parse(input: &str) -> Vec<Record> {
+    input
+        .lines()
+        .filter(|line| !line.starts_with("key,"))
+        .filter_map(parse_record)
+        .collect()
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+    for record in records {
+        writeln!(file, "{}", format_record(record))?;
+    }
+    Ok(())
+}
+
+/// The median of the most recent run of every part of `year`, by day and part.
+pub fn latest_medians(records: &[Record], year: i16) -> BTreeMap<(u8, u8), Duration> {
+    records
+        .iter()
+        .filter(|r| r.year == year && r.part > 0)
+        .map(|r| ((r.day, r.part), r.stats.median))
+        .collect()
+}
+
+/// Keys in the order they were first recorded.
+pub fn keys(records: &[Record]) -> Vec<&str> {
+    let mut keys: Vec<&str> = vec![];
+    for r in records {
+        if !keys.contains(&r.key.as_str()) {
+            keys.push(&r.key);
+        }
+    }
+    keys
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Faster,
+    Slower,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: i16,
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change of the median, e.g. `0.25` for 25% slower.
+    pub ratio: f64,
+    pub change: Change,
+}
+
+/// Compares the medians of two recorded runs. Changes within `threshold` (a fraction, e.g.
+/// `0.05` for 5%) count as unchanged. Later records for the same key override earlier ones.
+pub fn compare(
+    records: &[Record],
+    baseline: &str,
+    current: &str,
+    threshold: f64,
+) -> Vec<Comparison> {
+    let latest = |key: &str, year: i16, day: u8, part: u8| {
+        records
+            .iter()
+            .rev()
+            .find(|r| r.key == key && r.year == year && r.day == day && r.part == part)
+    };
+
+    let mut steps: Vec<(i16, u8, u8)> = records
+        .iter()
+        .filter(|r| r.key == current)
+        .map(|r| (r.year, r.day, r.part))
+        .collect();
+    steps.sort();
+    steps.dedup();
+
+    steps
+        .into_iter()
+        .filter_map(|(year, day, part)| {
+            let baseline = latest(baseline, year, day, part)?.stats.median;
+            let current = latest(current, year, day, part)?.stats.median;
+            let ratio = if baseline.is_zero() {
+                0_f64
+            } else {
+                current.as_secs_f64() / baseline.as_secs_f64() - 1_f64
+            };
+            let change = if ratio > threshold {
+                Change::Slower
+            } else if ratio < -threshold {
+                Change::Faster
+            } else {
+                Change::Unchanged
+            };
+            Some(Comparison {
+                year,
+                day,
+                part,
+                baseline,
+                current,
+                ratio,
+                change,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const HISTORY: &str = indoc! {"
+        key,year,day,part,min_ns,median_ns,mean_ns,stddev_ns,iterations
+        abc123,2022,1,0,39,40,41,2,1000
+        abc123,2022,1,1,999,1000,1100,50,1000
+        abc123,2022,1,2,1999,2000,2100,50,1000
+        abc123,2022,17,1,499999,500000,510000,900,40
+        def456,2022,1,0,39,40,41,2,1000
+        def456,2022,1,1,1029,1030,1100,50,1000
+        def456,2022,1,2,999,1000,1100,50,1000
+        def456,2022,17,1,899999,900000,910000,900,40
+        def456,2022,17,2,899999,900000,910000,900,40
+        def456,2021,17,2,899999,900000,910000,900,40
+    "};
+
+    #[test]
+    fn test_parse_round_trip() {
+        let records = parse(HISTORY);
+        assert_eq!(records.len(), 10);
+        assert_eq!(records[1].year, 2022);
+        assert_eq!(records[1].day, 1);
+        assert_eq!(records[1].part, 1);
+        assert_eq!(records[1].stats.median, Duration::from_nanos(1000));
+        assert_eq!(
+            format_record(&records[3]),
+            "abc123,2022,17,1,499999,500000,510000,900,40"
+        );
+        assert_eq!(keys(&records), vec!["abc123", "def456"]);
+    }
+
+    #[test]
+    fn test_latest_medians() {
+        let medians = latest_medians(&parse(HISTORY), 2022);
+        assert_eq!(medians.len(), 4);
+        assert_eq!(medians[&(1, 2)], Duration::from_nanos(1000));
+        assert_eq!(medians[&(17, 1)], Duration::from_nanos(900000));
+    }
+
+    #[test]
+    fn test_compare() {
+        let records = parse(HISTORY);
+        let changes: Vec<(i16, u8, u8, Change)> = compare(&records, "abc123", "def456", 0.05)
+            .iter()
+            .map(|c| (c.year, c.day, c.part, c.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (2022, 1, 0, Change::Unchanged),
+                (2022, 1, 1, Change::Unchanged),
+                (2022, 1, 2, Change::Faster),
+                (2022, 17, 1, Change::Slower),
+            ]
+        );
+    }
+}
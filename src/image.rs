@@ -0,0 +1,373 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use num_integer::Integer;
+use num_iter::range_inclusive;
+use num_traits::ToPrimitive;
+
+use crate::sparse_table::SparseTable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(0, 153, 0);
+    pub const BLUE: Color = Color(38, 139, 210);
+    pub const YELLOW: Color = Color(255, 255, 102);
+}
+
+/// Maps the characters of a rendered grid to colors, e.g. `#` to gray for rock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: HashMap<char, Color>,
+    default: Color,
+}
+
+impl Palette {
+    /// A palette drawing every character in `default`.
+    pub fn new(default: Color) -> Self {
+        Palette {
+            colors: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, tile: char, color: Color) -> Self {
+        self.colors.insert(tile, color);
+        self
+    }
+
+    pub fn color(&self, tile: char) -> Color {
+        self.colors.get(&tile).copied().unwrap_or(self.default)
+    }
+}
+
+/// A grid of colors, one per tile, such as one step of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Frame {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// A frame with the color of every `(row, col)` given by `color`.
+    pub fn from_fn(width: usize, height: usize, color: impl Fn((usize, usize)) -> Color) -> Self {
+        let pixels = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(color)
+            .collect();
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// A frame of text such as the output of a `Display` implementation, one tile per
+    /// character. Shorter lines are padded with the color of a space.
+    pub fn from_text(text: &str, palette: &Palette) -> Self {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        Frame::from_fn(width, lines.len(), |(row, col)| {
+            palette.color(lines[row].get(col).copied().unwrap_or(' '))
+        })
+    }
+
+    /// A frame of every cell within the bounds of a table, including its defaults.
+    pub fn from_table<N, T>(table: &SparseTable<N, T>, color: impl Fn(&T) -> Color) -> Self
+    where
+        N: Integer + Hash + Copy + ToPrimitive,
+        T: Clone,
+    {
+        let pixels: Vec<Color> = range_inclusive(*table.row_min(), *table.row_max())
+            .flat_map(|row| {
+                range_inclusive(*table.col_min(), *table.col_max()).map(move |col| (row, col))
+            })
+            .map(|cell| color(&table.resolve(cell)))
+            .collect();
+        let width = range_inclusive(*table.col_min(), *table.col_max()).count();
+        Frame {
+            width,
+            height: pixels.len() / width,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<Color> {
+        (row < self.height && col < self.width).then(|| self.pixels[row * self.width + col])
+    }
+
+    /// Colors a single tile, e.g. to highlight the current position. Tiles outside the
+    /// frame are ignored.
+    pub fn set(&mut self, (row, col): (usize, usize), color: Color) {
+        if row < self.height && col < self.width {
+            self.pixels[row * self.width + col] = color;
+        }
+    }
+
+    /// The frame with every tile drawn as a square of `scale` by `scale` pixels, since
+    /// single pixels are hard to make out.
+    pub fn scaled(&self, scale: usize) -> Frame {
+        Frame::from_fn(self.width * scale, self.height * scale, |(row, col)| {
+            self.pixels[row / scale * self.width + col / scale]
+        })
+    }
+
+    fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&Color(r, g, b)| [r, g, b])
+            .collect()
+    }
+
+    /// The frame as a binary PPM image, which most image viewers open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.rgb());
+        ppm
+    }
+
+    pub fn write_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_ppm())
+    }
+
+    pub fn write_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let (width, height) = (dimension(self.width)?, dimension(self.height)?);
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.rgb())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+fn dimension<D: TryFrom<usize>>(size: usize) -> io::Result<D> {
+    D::try_from(size).map_err(|_| io::Error::other(format!("{size} pixels is too large")))
+}
+
+/// The image formats frames can be written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Ppm,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Ppm => "ppm",
+        }
+    }
+}
+
+/// A sequence of frames of the same size, shown `delay` apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub frames: Vec<Frame>,
+    pub delay: Duration,
+}
+
+impl Animation {
+    pub fn new(delay: Duration) -> Self {
+        Animation {
+            frames: vec![],
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    /// Writes every frame to `folder` as `0000.png`, `0001.png` and so on, creating the
+    /// folder if needed. Returns the paths of the written files.
+    pub fn write_frames(
+        &self,
+        folder: impl AsRef<Path>,
+        format: Format,
+    ) -> io::Result<Vec<PathBuf>> {
+        let folder = folder.as_ref();
+        fs::create_dir_all(folder)?;
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let path = folder.join(format!("{i:04}.{}", format.extension()));
+                match format {
+                    Format::Png => frame.write_png(&path)?,
+                    Format::Ppm => frame.write_ppm(&path)?,
+                }
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Writes the frames as a looping animated GIF. Fails if the frames differ in size
+    /// or use more than 256 colors between them.
+    pub fn write_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let Some(first) = self.frames.first() else {
+            return Err(io::Error::other("an animation needs at least one frame"));
+        };
+        if self
+            .frames
+            .iter()
+            .any(|frame| (frame.width, frame.height) != (first.width, first.height))
+        {
+            return Err(io::Error::other("all frames must have the same size"));
+        }
+
+        let mut colors: Vec<Color> = vec![];
+        let mut indices: HashMap<Color, u8> = HashMap::new();
+        for &color in self.frames.iter().flat_map(|frame| &frame.pixels) {
+            if let Entry::Vacant(entry) = indices.entry(color) {
+                let index = u8::try_from(colors.len())
+                    .map_err(|_| io::Error::other("a GIF can only use 256 colors"))?;
+                entry.insert(index);
+                colors.push(color);
+            }
+        }
+        let palette: Vec<u8> = colors
+            .iter()
+            .flat_map(|&Color(r, g, b)| [r, g, b])
+            .collect();
+
+        let (width, height) = (dimension(first.width)?, dimension(first.height)?);
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            gif::Encoder::new(file, width, height, &palette).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        // GIF delays are in hundredths of a second.
+        let delay = u16::try_from(self.delay.as_millis() / 10).unwrap_or(u16::MAX);
+        for frame in &self.frames {
+            let pixels: Vec<u8> = frame.pixels.iter().map(|color| indices[color]).collect();
+            let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+            gif_frame.delay = delay;
+            encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+        }
+        encoder.into_inner()?.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        Palette::new(Color::BLACK)
+            .with('#', Color::GRAY)
+            .with('o', Color::YELLOW)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}_{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_from_text() {
+        let frame = Frame::from_text("#o\n#\n", &palette());
+        assert_eq!((frame.width(), frame.height()), (2, 2));
+        assert_eq!(frame.get((0, 1)), Some(Color::YELLOW));
+        assert_eq!(frame.get((1, 0)), Some(Color::GRAY));
+        assert_eq!(frame.get((1, 1)), Some(Color::BLACK));
+        assert_eq!(frame.get((2, 0)), None);
+    }
+
+    #[test]
+    fn test_from_table() {
+        let mut table = SparseTable::new_with_start_point('.', (0, 500));
+        table.insert((1, 499), '#');
+        table.add_row_default(2, 'o');
+        let frame = Frame::from_table(&table, |&tile| palette().color(tile));
+        assert_eq!((frame.width(), frame.height()), (2, 3));
+        assert_eq!(frame.get((1, 0)), Some(Color::GRAY));
+        assert_eq!(frame.get((2, 1)), Some(Color::YELLOW));
+    }
+
+    #[test]
+    fn test_scaled_ppm() {
+        let mut frame = Frame::new(2, 1, Color::BLACK);
+        frame.set((0, 1), Color(1, 2, 3));
+        frame.set((5, 5), Color::WHITE);
+
+        let scaled = frame.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.get((1, 3)), Some(Color(1, 2, 3)));
+        assert_eq!(scaled.get((1, 1)), Some(Color::BLACK));
+
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend([0, 0, 0, 1, 2, 3]);
+        assert_eq!(frame.to_ppm(), expected);
+    }
+
+    #[test]
+    fn test_write_png_and_gif() {
+        let dir = temp_dir("image_test");
+        let mut animation = Animation::new(Duration::from_millis(100));
+        animation.push(Frame::from_text("#o\n..\n", &palette()));
+        animation.push(Frame::from_text("#.\n.o\n", &palette()));
+
+        let paths = animation
+            .write_frames(dir.join("frames"), Format::Png)
+            .unwrap();
+        assert_eq!(paths[1], dir.join("frames").join("0001.png"));
+        let decoder = png::Decoder::new(File::open(&paths[0]).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(&pixels[..6], &[128, 128, 128, 255, 255, 102]);
+
+        animation.write_gif(dir.join("animation.gif")).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options
+            .read_info(File::open(dir.join("animation.gif")).unwrap())
+            .unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        animation.push(Frame::new(1, 1, Color::BLACK));
+        assert!(animation.write_gif(dir.join("mismatched.gif")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::{self, Display};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Something about the shape of an input that a downloaded input never has, and that
+/// parsers splitting on `"\n"` or `"\n\n"` trip over. Usually a sign that the input was
+/// copied from a browser or saved on Windows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    ByteOrderMark,
+    /// The number of lines ending in `\r\n`.
+    CrlfLineEndings(usize),
+    /// The number of lines ending in spaces or tabs, and the first of them (1-based).
+    TrailingWhitespace {
+        lines: usize,
+        first: usize,
+    },
+    MissingTrailingNewline,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::ByteOrderMark => write!(f, "starts with a byte order mark"),
+            Issue::CrlfLineEndings(lines) => write!(f, "has CRLF line endings on {lines} line(s)"),
+            Issue::TrailingWhitespace { lines, first } => write!(
+                f,
+                "has trailing whitespace on {lines} line(s), starting at line {first}"
+            ),
+            Issue::MissingTrailingNewline => write!(f, "does not end with a newline"),
+        }
+    }
+}
+
+impl Issue {
+    /// Whether `normalize` fixes this issue with the given options.
+    pub fn is_fixed_by(&self, normalization: &Normalization) -> bool {
+        match self {
+            Issue::ByteOrderMark => normalization.byte_order_mark,
+            Issue::CrlfLineEndings(_) => normalization.line_endings,
+            Issue::TrailingWhitespace { .. } => normalization.trailing_whitespace,
+            Issue::MissingTrailingNewline => normalization.trailing_newline,
+        }
+    }
+}
+
+/// Lists the issues of an input, in the order they are listed in [`Issue`].
+pub fn diagnose(input: &str) -> Vec<Issue> {
+    let mut issues = vec![];
+    if input.starts_with(BYTE_ORDER_MARK) {
+        issues.push(Issue::ByteOrderMark);
+    }
+    let crlf = input.matches("\r\n").count();
+    if crlf > 0 {
+        issues.push(Issue::CrlfLineEndings(crlf));
+    }
+    let trailing: Vec<usize> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.ends_with([' ', '\t']))
+        .map(|(i, _)| i + 1)
+        .collect();
+    if let Some(&first) = trailing.first() {
+        issues.push(Issue::TrailingWhitespace {
+            lines: trailing.len(),
+            first,
+        });
+    }
+    if !input.is_empty() && !input.ends_with('\n') {
+        issues.push(Issue::MissingTrailingNewline);
+    }
+    issues
+}
+
+/// Which issues `normalize` fixes. The default fixes everything but trailing whitespace,
+/// which some puzzles rely on, such as the padded crate diagram of 2022 day 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    pub byte_order_mark: bool,
+    /// Converts `\r\n` to `\n`.
+    pub line_endings: bool,
+    pub trailing_whitespace: bool,
+    pub trailing_newline: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            byte_order_mark: true,
+            line_endings: true,
+            trailing_whitespace: false,
+            trailing_newline: true,
+        }
+    }
+}
+
+impl Normalization {
+    /// Also strips spaces and tabs from the end of every line.
+    pub fn with_trailing_whitespace(self) -> Self {
+        Normalization {
+            trailing_whitespace: true,
+            ..self
+        }
+    }
+}
+
+/// Fixes the issues of `input` selected by `normalization`.
+pub fn normalize(input: &str, normalization: &Normalization) -> String {
+    let input = match input.strip_prefix(BYTE_ORDER_MARK) {
+        Some(rest) if normalization.byte_order_mark => rest,
+        _ => input,
+    };
+
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.split_inclusive('\n') {
+        let (content, ending) = match line.strip_suffix("\r\n") {
+            Some(content) if normalization.line_endings => (content, "\n"),
+            Some(content) => (content, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(content) => (content, "\n"),
+                None => (line, ""),
+            },
+        };
+        let content = if normalization.trailing_whitespace {
+            content.trim_end_matches([' ', '\t'])
+        } else {
+            content
+        };
+        normalized.push_str(content);
+        normalized.push_str(ending);
+    }
+
+    if normalization.trailing_newline && !normalized.is_empty() && !normalized.ends_with('\n') {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose() {
+        assert_eq!(diagnose("1\n2\n"), vec![]);
+        assert_eq!(diagnose(""), vec![]);
+        assert_eq!(
+            diagnose("\u{feff}1 \r\n2\r\n3\t\r\n4"),
+            vec![
+                Issue::ByteOrderMark,
+                Issue::CrlfLineEndings(3),
+                Issue::TrailingWhitespace { lines: 2, first: 1 },
+                Issue::MissingTrailingNewline,
+            ]
+        );
+        assert_eq!(
+            Issue::TrailingWhitespace { lines: 2, first: 1 }.to_string(),
+            "has trailing whitespace on 2 line(s), starting at line 1"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let input = "\u{feff}    [D] \r\n[N] [C]\r\n\r\nmove 1";
+        assert_eq!(
+            normalize(input, &Normalization::default()),
+            "    [D] \n[N] [C]\n\nmove 1\n"
+        );
+        assert_eq!(
+            normalize(input, &Normalization::default().with_trailing_whitespace()),
+            "    [D]\n[N] [C]\n\nmove 1\n"
+        );
+        assert_eq!(normalize("1\n2\n", &Normalization::default()), "1\n2\n");
+        assert_eq!(normalize("", &Normalization::default()), "");
+
+        let nothing = Normalization {
+            byte_order_mark: false,
+            line_endings: false,
+            trailing_whitespace: false,
+            trailing_newline: false,
+        };
+        assert_eq!(normalize(input, &nothing), input);
+    }
+
+    #[test]
+    fn test_is_fixed_by() {
+        let normalization = Normalization::default();
+        for issue in diagnose("\u{feff}1 \r\n2") {
+            let fixed = normalize("\u{feff}1 \r\n2", &normalization);
+            assert_eq!(
+                issue.is_fixed_by(&normalization),
+                !diagnose(&fixed).contains(&issue)
+            );
+        }
+    }
+}
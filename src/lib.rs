@@ -5,56 +5,133 @@
  */
 use std::env;
 use std::fs;
-
+use std::path::PathBuf;
+
+use checksum::InputState;
+use error::Error;
+use input::Normalization;
+
+pub mod answers;
+pub mod aoc;
+pub mod bench;
+pub mod checksum;
+pub mod days;
+pub mod error;
 pub mod helpers;
+pub mod history;
+pub mod image;
+pub mod input;
+pub mod params;
+pub mod puzzle;
+pub mod readme;
+pub mod runner;
+pub mod selection;
+pub mod solution;
 pub mod sparse_table;
+pub mod submission;
+pub mod viewer;
+
+/// The year used when no `--year` is given.
+pub const YEAR: i16 = 2022;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Runs a part and prints its answer and how long it took, like the runner does.
+/// `$solver` is a function taking the input and returning an `Option` or a `Result` of
+/// the answer. A `Result<Option<T>, E>` needs the answer type `T` as a fourth argument.
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
-
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        // solving outside of a day, so there is no day to report.
+        let result = $crate::runner::run_fn(0, $part, || {
+            $crate::error::IntoAnswer::into_answer($solver($input))
+                .map(|answer| answer.map(|answer| answer.to_string()))
+        });
+        $crate::runner::print_part(&result);
+    }};
+    ($part:expr, $solver:ident, $input:expr, $answer:ty) => {{
+        let result = $crate::runner::run_fn(0, $part, || {
+            $crate::error::IntoAnswer::<$answer>::into_answer($solver($input))
+                .map(|answer| answer.map(|answer| answer.to_string()))
+        });
+        $crate::runner::print_part(&result);
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
+/// The folder holding the files of `year`, e.g. `src/inputs/2022`.
+pub fn year_path(folder: &str, year: i16) -> PathBuf {
     let cwd = env::current_dir().unwrap();
 
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
+    cwd.join("src").join(folder).join(year.to_string())
+}
+
+pub fn file_path(folder: &str, year: i16, day: u8) -> PathBuf {
+    file_path_with_suffix(folder, year, day, "")
+}
+
+/// The path of an additional file of a day, e.g. `src/examples/2022/09-2.txt` for the
+/// suffix `-2` or `src/examples/2022/09.larger.txt` for `.larger`.
+pub fn file_path_with_suffix(folder: &str, year: i16, day: u8, suffix: &str) -> PathBuf {
+    year_path(folder, year).join(format!("{day:02}{suffix}.txt"))
+}
+
+/// Reads the real input of a day, warning if it no longer matches its recorded checksum or
+/// has a shape a downloaded input never has. CRLF line endings and the like are normalized.
+pub fn read_input(year: i16, day: u8) -> Result<String, Error> {
+    let input = fs::read_to_string(file_path("inputs", year, day))
+        .map_err(|e| Error::new(format!("Could not read input file: {e}")))?;
+    let state = checksum::check(year, day, &input);
+    match state {
+        Ok(InputState::Truncated) => {
+            eprintln!("Warning: input of day {day:02} is shorter than when it was downloaded.")
+        }
+        Ok(InputState::Edited) => {
+            eprintln!("Warning: input of day {day:02} was changed since it was downloaded.")
+        }
+        _ => {}
+    }
+
+    let normalization = Normalization::default();
+    // a downloaded input is exactly what the puzzle expects, whatever its shape.
+    if !matches!(state, Ok(InputState::Intact)) {
+        for issue in input::diagnose(&input) {
+            let fixed = if issue.is_fixed_by(&normalization) {
+                " (normalized)"
+            } else {
+                ""
+            };
+            eprintln!("Warning: input of day {day:02} {issue}{fixed}.");
+        }
+    }
+    Ok(input::normalize(&input, &normalization))
+}
+
+/// Reads a file of a day, normalizing CRLF line endings, a byte order mark and a missing
+/// trailing newline, so examples saved on Windows parse like downloaded ones.
+pub fn read_file(folder: &str, year: i16, day: u8) -> Result<String, Error> {
+    read_file_with_suffix(folder, year, day, "")
+}
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// Reads an additional file of a day, such as a second example used by part two.
+pub fn read_file_with_suffix(
+    folder: &str,
+    year: i16,
+    day: u8,
+    suffix: &str,
+) -> Result<String, Error> {
+    let path = file_path_with_suffix(folder, year, day, suffix);
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| Error::new(format!("could not read \"{}\": {e}", path.display())))?;
+    Ok(input::normalize(&contents, &Normalization::default()))
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
 
+/// Sums the `(elapsed: ...)` timings printed by [`solve!`] in `output`, in milliseconds.
 pub fn parse_exec_time(output: &str) -> f64 {
     output.lines().fold(0_f64, |acc, l| {
         if !l.contains("elapsed:") {
@@ -96,6 +173,22 @@ macro_rules! assert_approx_eq {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_solve() {
+        fn part_one(input: &str) -> Option<usize> {
+            Some(input.len())
+        }
+        fn part_two(input: &str) -> error::Result<Option<usize>> {
+            Ok(input.find('c'))
+        }
+        fn part_three(input: &str) -> Result<usize, String> {
+            input.parse().map_err(|_| String::from("not a number"))
+        }
+        solve!(1, part_one, "abc");
+        solve!(2, part_two, "abc", usize);
+        solve!(3, part_three, "abc");
+    }
+
     #[test]
     fn test_parse_exec_time() {
         assert_approx_eq!(
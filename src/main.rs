@@ -2,42 +2,545 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::answers::{self, Answers, Verdict};
+use advent_of_code::bench::{self, BenchOptions, DayBench, Stats};
+use advent_of_code::history::{self, Change};
+use advent_of_code::params::{self, Params};
+use advent_of_code::runner::{self, Format, PartResult, Status};
+use advent_of_code::selection::{self, Selection};
+use advent_of_code::solution::DynSolution;
+use advent_of_code::{days, readme, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{fs, process};
 
-fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{day:02}");
+struct Args {
+    day: Option<u8>,
+    selection: Selection,
+    verify: bool,
+    bench: bool,
+    readme: bool,
+    iterations: Option<NonZeroUsize>,
+    compare: Option<String>,
+    current: Option<String>,
+    threshold: f64,
+    jobs: usize,
+    timeout: Option<Duration>,
+    child_part: Option<u8>,
+    format: Format,
+    /// Parameters of the day given as `--name value` after the day.
+    overrides: Vec<(String, String)>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut pargs = pico_args::Arguments::from_env();
+    let mut args = Args {
+        selection: Selection::from_args(&mut pargs)?,
+        verify: pargs.contains("--verify"),
+        bench: pargs.contains("--bench"),
+        readme: pargs.contains("--readme"),
+        iterations: pargs.opt_value_from_str("--iterations")?,
+        compare: pargs.opt_value_from_str("--compare")?,
+        current: pargs.opt_value_from_str("--current")?,
+        threshold: pargs.opt_value_from_str("--threshold")?.unwrap_or(5_f64),
+        jobs: pargs.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+        timeout: pargs
+            .opt_value_from_fn("--timeout", |s| s.parse().map(Duration::from_secs_f64))?,
+        child_part: pargs.opt_value_from_str(runner::CHILD_FLAG)?,
+        format: pargs
+            .opt_value_from_str("--format")?
+            .unwrap_or(Format::Human),
+        day: None,
+        overrides: vec![],
+    };
+
+    // what is left is an optional day, followed by parameter overrides.
+    let mut rest = pargs
+        .finish()
+        .into_iter()
+        .map(|arg| arg.into_string())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| pico_args::Error::NonUtf8Argument)?;
+    if rest.first().is_some_and(|arg| !arg.starts_with('-')) {
+        let day = rest.remove(0);
+        args.day =
+            Some(selection::parse_day(&day).map_err(|cause| {
+                pico_args::Error::Utf8ArgumentParsingFailed { value: day, cause }
+            })?);
+    }
+    args.overrides = params::parse_overrides(&rest)
+        .map_err(|cause| pico_args::Error::ArgumentParsingFailed { cause })?;
+
+    // a single day given as a free argument takes precedence over `--days`.
+    if let Some(day) = args.day {
+        args.selection.days = vec![day];
+    }
+    Ok(args)
+}
+
+/// Loads a day along with its input and the parameters for the real input, after
+/// applying `overrides`.
+fn load_day(
+    year: i16,
+    day: u8,
+    overrides: &[(String, String)],
+) -> Result<(&'static dyn DynSolution, String, Params), String> {
+    let solution = days::get(year, day).ok_or_else(|| String::from("Not solved."))?;
+    let params = Params::real(solution.params()).with_overrides(solution.params(), overrides)?;
+    let input = advent_of_code::read_input(year, day).map_err(|e| e.to_string())?;
+    Ok((solution, input, params))
+}
+
+/// Runs the given parts of a day, each in its own child process if a `timeout` is given.
+/// Fails with a human-readable reason if the day cannot be run.
+fn run_day(
+    year: i16,
+    day: u8,
+    parts: &[u8],
+    overrides: &[(String, String)],
+    timeout: Option<Duration>,
+) -> Result<Vec<PartResult>, String> {
+    let (solution, input, params) = load_day(year, day, overrides)?;
+    Ok(parts
+        .iter()
+        .map(|&part| match timeout {
+            Some(timeout) => runner::run_part_isolated(year, day, part, overrides, timeout),
+            None => runner::run_part(solution, part, &input, &params),
+        })
+        .collect())
+}
+
+/// Runs the planned parts of every day on `jobs` threads, reporting days that cannot be
+/// run as not solved.
+fn collect_results(
+    year: i16,
+    plan: &[(u8, Vec<u8>)],
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Vec<PartResult> {
+    let mut results = vec![];
+    runner::run_ordered(
+        plan,
+        jobs,
+        |(day, parts)| run_day(year, *day, parts, &[], timeout),
+        |(day, parts), day_results| {
+            // a day without a solution is not solved, any other reason it cannot run fails it.
+            let solved = days::get(year, *day).is_some();
+            results.extend(day_results.unwrap_or_else(|e| {
+                parts
+                    .iter()
+                    .map(|&part| {
+                        if solved {
+                            PartResult::failed(*day, part, e.clone())
+                        } else {
+                            PartResult::not_solved(*day, part)
+                        }
+                    })
+                    .collect()
+            }))
+        },
+    );
+    results
+}
+
+/// Regenerates the stars and benchmark tables in `README.md` from a run of all days,
+/// regardless of any selection.
+fn update_readme(year: i16, timeout: Option<Duration>) {
+    // sequential, so the recorded timings are not skewed by other days.
+    let selection = Selection {
+        year,
+        ..Selection::default()
+    };
+    let results = collect_results(year, &selection.plan(), 1, timeout);
+    let medians = match history::load(Path::new(history::HISTORY_FILE)) {
+        Ok(records) => history::latest_medians(&records, year),
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", history::HISTORY_FILE);
+            Default::default()
+        }
+    };
+    let updated = fs::read_to_string("README.md")
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            readme::replace_section(
+                &contents,
+                readme::STARS_MARKER,
+                &readme::stars_table(&results, year),
+            )
+        })
+        .and_then(|contents| {
+            readme::replace_section(
+                &contents,
+                readme::BENCHMARK_MARKER,
+                &readme::benchmark_table(&results, &medians, year),
+            )
+        })
+        .and_then(|contents| fs::write("README.md", contents).map_err(|e| e.to_string()));
 
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
+    match updated {
+        Ok(_) => println!("Updated stars and benchmark tables in \"README.md\"."),
+        Err(e) => {
+            eprintln!("Failed to update \"README.md\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the planned days on `jobs` threads and prints each day in order once it is done.
+fn run_all(year: i16, plan: &[(u8, Vec<u8>)], jobs: usize, timeout: Option<Duration>) {
+    let timer = Instant::now();
+    let mut total = Duration::ZERO;
+    let mut statuses: Vec<Status> = vec![];
 
+    runner::run_ordered(
+        plan,
+        jobs,
+        |(day, parts)| run_day(year, *day, parts, &[], timeout),
+        |(day, parts), results| {
             println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
+            println!("{ANSI_BOLD}| Day {day:02} |{ANSI_RESET}");
             println!("----------");
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+            match results {
+                Ok(results) => {
+                    results.iter().for_each(runner::print_part);
+                    statuses.extend(results.iter().map(|r| r.status.clone()));
+                    total += results
+                        .iter()
+                        .filter(|r| r.status == Status::Solved)
+                        .map(|r| r.elapsed)
+                        .sum::<Duration>();
                 }
-            );
+                Err(e) => {
+                    println!("{e}");
+                    statuses.extend(parts.iter().map(|_| Status::NotSolved));
+                }
+            }
+        },
+    );
+
+    let count = |name| statuses.iter().filter(|s| s.name() == name).count();
+    println!(
+        "{ANSI_BOLD}Parts:{ANSI_RESET} {} solved, {} not solved, {} failed, {} panicked, {} timed out",
+        count("solved"),
+        count("not_solved"),
+        count("failed"),
+        count("panicked"),
+        count("timed_out")
+    );
+
+    println!(
+        "{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+        total.as_secs_f64() * 1000_f64
+    );
+    if jobs > 1 {
+        println!(
+            "{ANSI_BOLD}Wall clock:{ANSI_RESET} {ANSI_ITALIC}{:.2}ms ({jobs} jobs){ANSI_RESET}",
+            timer.elapsed().as_secs_f64() * 1000_f64
+        );
+    }
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "{ANSI_ITALIC}(min: {:.2?}, median: {:.2?}, mean: {:.2?}, stddev: {:.2?}, iterations: {}){ANSI_RESET}",
+        stats.min, stats.median, stats.mean, stats.stddev, stats.iterations
+    )
+}
+
+fn print_bench(day_bench: &DayBench) {
+    if let Some(stats) = &day_bench.parse {
+        println!("🎄 {ANSI_BOLD}Parse{ANSI_RESET} 🎄");
+        println!("{}", format_stats(stats));
+    }
+    for part in &day_bench.parts {
+        println!("🎄 {ANSI_BOLD}Part {}{ANSI_RESET} 🎄", part.result.part);
+        match (&part.result.status, &part.result.answer, &part.stats) {
+            (Status::Solved, Some(answer), Some(stats)) => {
+                println!("{answer} {}", format_stats(stats))
+            }
+            (Status::Failed(message), _, _) => println!("error: {message}"),
+            (Status::Panicked(message), _, _) => println!("panicked: {message}"),
+            _ => println!("not solved."),
+        }
+    }
+}
+
+/// Benchmarks the planned days and records the results in the history file.
+/// Parse time is reported separately from the parts, for days whose `parse` does any work.
+fn run_bench(
+    year: i16,
+    plan: &[(u8, Vec<u8>)],
+    overrides: &[(String, String)],
+    options: &BenchOptions,
+) {
+    let mut day_benches = vec![];
+    let total: Duration = plan
+        .iter()
+        .map(|(day, parts)| {
+            if plan.len() > 1 {
+                println!("----------");
+                println!("{ANSI_BOLD}| Day {day:02} |{ANSI_RESET}");
+                println!("----------");
+            }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+            match load_day(year, *day, overrides) {
+                Ok((solution, input, params)) => {
+                    let day_bench = bench::bench_day(solution, &input, &params, parts, options);
+                    print_bench(&day_bench);
+                    let total = day_bench.total_median();
+                    day_benches.push(day_bench);
+                    total
+                }
+                Err(e) => {
+                    println!("{e}");
+                    Duration::ZERO
+                }
             }
         })
         .sum();
 
-    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+    println!(
+        "{ANSI_BOLD}Total (medians):{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+        total.as_secs_f64() * 1000_f64
+    );
+
+    let key = history::current_key();
+    let records: Vec<history::Record> = day_benches
+        .iter()
+        .flat_map(|day_bench| history::records(&key, year, day_bench))
+        .collect();
+    match history::append(Path::new(history::HISTORY_FILE), &records) {
+        Ok(_) => println!(
+            "Recorded results as \"{key}\" in \"{}\"",
+            history::HISTORY_FILE
+        ),
+        Err(e) => eprintln!(
+            "Failed to record results in \"{}\": {e}",
+            history::HISTORY_FILE
+        ),
+    }
+}
+
+/// Reports which days got faster or slower between two recorded runs. `current` defaults
+/// to the most recently recorded run. Exits with a non-zero status if anything got slower.
+/// Only the planned parts are compared, along with the parse step of their days.
+fn compare(
+    year: i16,
+    plan: &[(u8, Vec<u8>)],
+    baseline: &str,
+    current: Option<&str>,
+    threshold_percent: f64,
+) {
+    let records = match history::load(Path::new(history::HISTORY_FILE)) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", history::HISTORY_FILE);
+            process::exit(1);
+        }
+    };
+    let keys = history::keys(&records);
+    let current = match current.or_else(|| records.last().map(|r| r.key.as_str())) {
+        Some(current) => current,
+        None => {
+            eprintln!("No benchmark results recorded yet. Run with `--bench` first.");
+            process::exit(1);
+        }
+    };
+    for key in [baseline, current] {
+        if !keys.contains(&key) {
+            eprintln!(
+                "No results recorded for \"{key}\". Known: {}",
+                keys.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+
+    println!(
+        "Comparing \"{current}\" against baseline \"{baseline}\" (threshold: {threshold_percent}%)"
+    );
+    let comparisons: Vec<_> =
+        history::compare(&records, baseline, current, threshold_percent / 100_f64)
+            .into_iter()
+            .filter(|c| c.year == year)
+            .filter(|c| {
+                plan.iter()
+                    .any(|(day, parts)| *day == c.day && (c.part == 0 || parts.contains(&c.part)))
+            })
+            .collect();
+    for c in &comparisons {
+        let step = match c.part {
+            0 => String::from("parse"),
+            part => format!("part {part}"),
+        };
+        let change = match c.change {
+            Change::Faster => "faster",
+            Change::Slower => "SLOWER",
+            Change::Unchanged => "unchanged",
+        };
+        println!(
+            "Day {:02} {step}: {:.2?} -> {:.2?} ({:+.1}%) {change}",
+            c.day,
+            c.baseline,
+            c.current,
+            c.ratio * 100_f64
+        );
+    }
+
+    let slower = comparisons
+        .iter()
+        .filter(|c| c.change == Change::Slower)
+        .count();
+    if slower > 0 {
+        println!("---");
+        println!("{slower} step(s) got slower.");
+        process::exit(1);
+    }
+}
+
+/// Runs the given parts of a day against the recorded answers. Returns `false` on a
+/// regression.
+fn verify_day(year: i16, solution: &dyn DynSolution, parts: &[u8]) -> bool {
+    let day = solution.day();
+    let answers = match Answers::read(year, day) {
+        Ok(answers) => answers,
+        Err(e) => {
+            println!("Day {day:02}: could not read answer file: {e}");
+            return false;
+        }
+    };
+    let input = match advent_of_code::read_input(year, day) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Day {day:02}: {e}");
+            return answers.part_one.is_none() && answers.part_two.is_none();
+        }
+    };
+
+    let params = Params::real(solution.params());
+    let mut passed = true;
+    for &part in parts {
+        let result = runner::run_part(solution, part, &input, &params);
+        let status = match answers::verify(answers.get(part), result.answer.as_deref()) {
+            Verdict::Pass => String::from("pass"),
+            Verdict::Missing => String::from("missing"),
+            Verdict::Fail { expected, actual } => {
+                passed = false;
+                let actual = actual.unwrap_or_else(|| String::from(result.status.name()));
+                format!("{ANSI_BOLD}FAIL{ANSI_RESET} (expected {expected}, got {actual})")
+            }
+        };
+        println!("Day {day:02} part {part}: {status}");
+    }
+    passed
+}
+
+fn verify(year: i16, plan: &[(u8, Vec<u8>)]) {
+    let failures = plan
+        .iter()
+        .filter_map(|(day, parts)| days::get(year, *day).map(|solution| (solution, parts)))
+        .filter(|(solution, parts)| !verify_day(year, *solution, parts))
+        .count();
+
+    if failures > 0 {
+        println!("---");
+        println!("{failures} day(s) failed verification.");
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let year = args.selection.year;
+
+    if let (Some(day), Some(part)) = (args.day, args.child_part) {
+        match load_day(year, day, &args.overrides) {
+            Ok((solution, input, params)) => runner::run_child(solution, part, &input, &params),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // answers are recorded for the default parameters, so overrides only apply to
+    // running or benchmarking a single day.
+    if !args.overrides.is_empty() && (args.day.is_none() || args.verify || args.readme) {
+        eprintln!("Parameters can only be overridden when running a single day.");
+        process::exit(1);
+    }
+
+    let plan = args.selection.plan();
+
+    if args.verify {
+        verify(year, &plan);
+        return;
+    }
+
+    if args.bench {
+        let options = match args.iterations {
+            Some(iterations) => BenchOptions::default().with_iterations(iterations.get()),
+            None => BenchOptions::default(),
+        };
+        run_bench(year, &plan, &args.overrides, &options);
+    }
+
+    if let Some(baseline) = &args.compare {
+        compare(
+            year,
+            &plan,
+            baseline,
+            args.current.as_deref(),
+            args.threshold,
+        );
+    }
+
+    if args.bench || args.compare.is_some() {
+        return;
+    }
+
+    if args.readme {
+        update_readme(year, args.timeout);
+        return;
+    }
+
+    if args.format != Format::Human {
+        // isolated, so whatever the solvers print cannot end up in the output.
+        let timeout = args.timeout.unwrap_or(Duration::MAX);
+        let results = collect_results(year, &plan, args.jobs, Some(timeout));
+        match args.format {
+            Format::Json => println!("{}", runner::to_json(&results)),
+            _ => print!("{}", runner::to_csv(&results)),
+        }
+        return;
+    }
+
+    if let Some(day) = args.day {
+        match run_day(
+            year,
+            day,
+            &args.selection.parts(day),
+            &args.overrides,
+            args.timeout,
+        ) {
+            Ok(results) => results.iter().for_each(runner::print_part),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    run_all(year, &plan, args.jobs, args.timeout);
 }
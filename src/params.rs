@@ -0,0 +1,207 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A value of a puzzle that is not part of its input and differs between the example
+/// and the real input, such as the row to scan in 2022 day 15.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value used for the real input.
+    pub real: &'static str,
+    /// The value used for the example input.
+    pub example: &'static str,
+    /// Checks that a value parses as the type the day reads the parameter as.
+    pub validate: fn(&str) -> Result<(), String>,
+}
+
+impl Param {
+    /// Declares a parameter read as a `T`, e.g. `Param::new::<isize>("row", "2000000", "10")`.
+    pub const fn new<T>(name: &'static str, real: &'static str, example: &'static str) -> Self
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Param {
+            name,
+            real,
+            example,
+            validate: validate::<T>,
+        }
+    }
+}
+
+fn validate<T>(value: &str) -> Result<(), String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// The values of the parameters declared by a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<&'static str, String>);
+
+impl Params {
+    /// The values for the real input.
+    pub fn real(declared: &[Param]) -> Self {
+        Params(
+            declared
+                .iter()
+                .map(|p| (p.name, p.real.to_string()))
+                .collect(),
+        )
+    }
+
+    /// The values for the example input, used by the tests of a day.
+    pub fn example(declared: &[Param]) -> Self {
+        Params(
+            declared
+                .iter()
+                .map(|p| (p.name, p.example.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Replaces values with the given overrides. Fails if a day does not declare one of
+    /// the overridden parameters, or if a value does not parse.
+    pub fn with_overrides(
+        mut self,
+        declared: &[Param],
+        overrides: &[(String, String)],
+    ) -> Result<Self, String> {
+        for (name, value) in overrides {
+            let param = declared.iter().find(|p| p.name == name).ok_or_else(|| {
+                let known: Vec<&str> = declared.iter().map(|p| p.name).collect();
+                if known.is_empty() {
+                    format!("unknown parameter \"{name}\", this day has none")
+                } else {
+                    format!(
+                        "unknown parameter \"{name}\", expected one of {}",
+                        known.join(", ")
+                    )
+                }
+            })?;
+            (param.validate)(value)
+                .map_err(|e| format!("invalid value \"{value}\" for parameter \"{name}\": {e}"))?;
+            self.0.insert(param.name, value.clone());
+        }
+        Ok(self)
+    }
+
+    /// The value of a parameter. Panics if it is not declared or cannot be parsed as the
+    /// declared type, which is reported like any other panic of a solution.
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .0
+            .get(name)
+            .unwrap_or_else(|| panic!("parameter \"{name}\" is not declared"));
+        value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value \"{value}\" for parameter \"{name}\": {e}"))
+    }
+}
+
+/// Reads parameter overrides given as `--name value` or `--name=value`. Dashes in names
+/// are read as underscores, and a single `--` separator is skipped.
+pub fn parse_overrides(args: &[String]) -> Result<Vec<(String, String)>, String> {
+    let mut overrides = vec![];
+    let mut args = args.iter().filter(|arg| *arg != "--");
+    while let Some(arg) = args.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            return Err(format!("unexpected argument \"{arg}\""));
+        };
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name, value.to_string()),
+            None => match args.next() {
+                Some(value) => (name, value.clone()),
+                None => return Err(format!("missing value for \"{arg}\"")),
+            },
+        };
+        overrides.push((name.replace('-', "_"), value));
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new::<isize>("row", "2000000", "10"),
+        Param::new::<u32>("max", "4000000", "20"),
+    ];
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(Params::real(PARAMS).get::<isize>("row"), 2_000_000);
+        assert_eq!(Params::example(PARAMS).get::<isize>("row"), 10);
+        assert_eq!(Params::example(PARAMS).get::<u32>("max"), 20);
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let overrides = vec![(String::from("row"), String::from("11"))];
+        let params = Params::real(PARAMS)
+            .with_overrides(PARAMS, &overrides)
+            .unwrap();
+        assert_eq!(params.get::<isize>("row"), 11);
+        assert_eq!(params.get::<isize>("max"), 4_000_000);
+
+        let overrides = vec![(String::from("col"), String::from("1"))];
+        assert!(Params::real(PARAMS)
+            .with_overrides(PARAMS, &overrides)
+            .is_err());
+        assert!(Params::real(&[]).with_overrides(&[], &overrides).is_err());
+    }
+
+    #[test]
+    fn test_with_invalid_override() {
+        let overrides = vec![(String::from("row"), String::from("abc"))];
+        assert_eq!(
+            Params::real(PARAMS).with_overrides(PARAMS, &overrides),
+            Err(String::from(
+                "invalid value \"abc\" for parameter \"row\": invalid digit found in string"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        for param in crate::days::YEARS
+            .iter()
+            .flat_map(|(_, solutions)| solutions.iter())
+            .flat_map(|solution| solution.params())
+            .chain(PARAMS)
+        {
+            assert_eq!((param.validate)(param.real), Ok(()), "{}", param.name);
+            assert_eq!((param.validate)(param.example), Ok(()), "{}", param.name);
+        }
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        assert_eq!(
+            parse_overrides(&strings(&["--", "--row", "10", "--max-coordinate=4"])),
+            Ok(vec![
+                (String::from("row"), String::from("10")),
+                (String::from("max_coordinate"), String::from("4"))
+            ])
+        );
+        assert!(parse_overrides(&strings(&["10"])).is_err());
+        assert!(parse_overrides(&strings(&["--row"])).is_err());
+    }
+}
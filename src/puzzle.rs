@@ -0,0 +1,277 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+
+const ARTICLE_START: &str = "<article class=\"day-desc\">";
+const ARTICLE_END: &str = "</article>";
+
+/// The descriptions of the unlocked parts of a puzzle page, as raw HTML.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find(ARTICLE_START) {
+        rest = &rest[start + ARTICLE_START.len()..];
+        let end = rest.find(ARTICLE_END).unwrap_or(rest.len());
+        articles.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    articles
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Open(&'a str, &'a str),
+    Close(&'a str),
+}
+
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim_end_matches('/');
+        rest = &rest[start + end + 1..];
+
+        match tag.strip_prefix('/') {
+            Some(name) => tokens.push(Token::Close(name.trim())),
+            None => {
+                let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+                tokens.push(Token::Open(name, attributes));
+            }
+        }
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = start + attributes[start..].find('"')?;
+    Some(&attributes[start..end])
+}
+
+/// The text content of an HTML fragment, without any tags.
+pub(crate) fn text(html: &str) -> String {
+    tokens(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode_entities(text)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Converts the puzzle descriptions of a page to Markdown. Only the handful of tags used
+/// on Advent of Code puzzle pages are translated, all others are dropped.
+pub fn to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut links: Vec<String> = vec![];
+
+    for article in articles(html) {
+        for token in tokens(article) {
+            match token {
+                // whitespace between block elements
+                Token::Text(text) if !in_pre && text.trim().is_empty() && text.contains('\n') => {}
+                Token::Text(text) => markdown.push_str(&decode_entities(text)),
+                Token::Open("h2", _) => markdown.push_str("## "),
+                Token::Close("h2") | Token::Close("p") => markdown.push_str("\n\n"),
+                Token::Open("pre", _) => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                Token::Close("pre") => {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+                Token::Open("code", _) if !in_pre => {
+                    in_code = true;
+                    markdown.push('`');
+                }
+                Token::Close("code") if !in_pre => {
+                    in_code = false;
+                    markdown.push('`');
+                }
+                Token::Open("em", _) | Token::Close("em") if !in_pre && !in_code => {
+                    markdown.push('*')
+                }
+                Token::Open("li", _) => markdown.push_str("- "),
+                Token::Close("li") => markdown.push('\n'),
+                Token::Close("ul") => markdown.push('\n'),
+                Token::Open("a", attributes) => {
+                    links.push(
+                        attribute(attributes, "href")
+                            .unwrap_or_default()
+                            .to_string(),
+                    );
+                    markdown.push('[');
+                }
+                Token::Close("a") => {
+                    markdown.push_str(&format!("]({})", links.pop().unwrap_or_default()))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    format!("{}\n", markdown.trim_end())
+}
+
+/// The first preformatted block of the first part, which is usually the example input.
+pub fn example(html: &str) -> Option<String> {
+    let article = articles(html).into_iter().next()?;
+    let start = article.find("<pre>")? + "<pre>".len();
+    let end = start + article[start..].find("</pre>")?;
+    Some(text(&article[start..end]))
+}
+
+/// The last highlighted code of each part, which is usually the answer to its example.
+pub fn example_answers(html: &str) -> Vec<Option<String>> {
+    articles(html)
+        .into_iter()
+        .map(|article| {
+            ["<code><em>", "<em><code>"]
+                .iter()
+                .filter_map(|open| article.rfind(open).map(|start| (start, open.len())))
+                .max()
+                .and_then(|(start, len)| {
+                    let rest = &article[start + len..];
+                    let end = rest.find('<')?;
+                    Some(decode_entities(&rest[..end]))
+                })
+        })
+        .collect()
+}
+
+/// Replaces the scaffolded `None` assertion of a part in a day's tests with `answer`.
+/// Returns `None` if there is no untouched assertion or the answer is not a number.
+pub fn fill_in_assertion(source: &str, part: u8, answer: &str) -> Option<String> {
+    let name = match part {
+        1 => "part_one",
+        2 => "part_two",
+        _ => return None,
+    };
+    answer.parse::<i64>().ok()?;
+
+    // days with parameters call `part_one(&input, &Params::example(PARAMS))`.
+    let start = format!("assert_eq!({name}(&input");
+    let placeholder = source
+        .lines()
+        .find(|line| line.trim_start().starts_with(&start) && line.ends_with("), None);"))?;
+    let filled = format!("{}Some({answer}));", placeholder.trim_end_matches("None);"));
+    Some(source.replacen(placeholder, &filled, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const PAGE: &str = include_str!("fixtures/puzzle.html");
+
+    #[test]
+    fn test_articles() {
+        assert_eq!(articles(PAGE).len(), 2);
+        assert_eq!(articles("<main></main>").len(), 0);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown = to_markdown(PAGE);
+        assert!(markdown.starts_with(indoc! {"
+            ## --- Day 1: Calorie Counting ---
+
+            The Elves take turns writing down the number of *Calories* contained by the various meals, [snacks](https://en.wikipedia.org/wiki/Snack), and so on that they've brought with them, one item per line.
+
+            For example, suppose the Elves finish writing their items' Calories and end up with the following list:
+
+            ```
+            1000
+            2000
+            3000
+
+            4000
+            ```
+
+            This list represents the Calories of the food carried by two Elves:
+
+            - The first Elf is carrying food with `1000`, `2000`, and `3000` Calories, a total of `6000` Calories.
+            - The second Elf is carrying one food item with `4000` Calories.
+
+        "}));
+        assert!(markdown.contains("## --- Part Two ---"));
+        assert!(markdown.ends_with("carrying *in total*?\n"));
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            example(PAGE),
+            Some(String::from("1000\n2000\n3000\n\n4000\n"))
+        );
+        assert_eq!(example("<p>no puzzle</p>"), None);
+    }
+
+    #[test]
+    fn test_example_answers() {
+        assert_eq!(
+            example_answers(PAGE),
+            vec![Some(String::from("6000")), Some(String::from("10000"))]
+        );
+    }
+
+    #[test]
+    fn test_fill_in_assertion() {
+        let source = indoc! {"
+            let input = crate::read_file(\"examples\", 2022, 1).unwrap();
+            assert_eq!(part_one(&input), None);
+            assert_eq!(part_two(&input), None);
+        "};
+        assert_eq!(
+            fill_in_assertion(source, 1, "6000"),
+            Some(String::from(indoc! {"
+                let input = crate::read_file(\"examples\", 2022, 1).unwrap();
+                assert_eq!(part_one(&input), Some(6000));
+                assert_eq!(part_two(&input), None);
+            "}))
+        );
+        assert_eq!(fill_in_assertion(source, 2, "abc"), None);
+        assert_eq!(
+            fill_in_assertion(
+                "assert_eq!(part_two(&input, &Params::example(PARAMS)), None);",
+                2,
+                "56000011"
+            ),
+            Some(String::from(
+                "assert_eq!(part_two(&input, &Params::example(PARAMS)), Some(56000011));"
+            ))
+        );
+        assert_eq!(
+            fill_in_assertion("assert_eq!(part_two(&input), Some(1));", 2, "2"),
+            None
+        );
+    }
+}
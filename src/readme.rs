@@ -0,0 +1,233 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::runner::{PartResult, Status};
+
+pub const STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+pub const BENCHMARK_MARKER: &str = "<!--- benchmarking table --->";
+
+fn results_for(results: &[PartResult], day: u8) -> impl Iterator<Item = &PartResult> {
+    results.iter().filter(move |r| r.day == day)
+}
+
+fn is_solved(results: &[PartResult], day: u8, part: u8) -> bool {
+    results_for(results, day).any(|r| r.part == part && r.status == Status::Solved)
+}
+
+fn solved_days(results: &[PartResult]) -> Vec<u8> {
+    (1..=25)
+        .filter(|&day| (1..=2).any(|part| is_solved(results, day, part)))
+        .collect()
+}
+
+/// Lists every day with at least one solved part, like the `advent-readme-stars` action.
+pub fn stars_table(results: &[PartResult], year: i16) -> String {
+    let mut table =
+        format!("## {year} Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n");
+    for day in solved_days(results) {
+        let star = |part| {
+            if is_solved(results, day, part) {
+                "⭐"
+            } else {
+                " "
+            }
+        };
+        table.push_str(&format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |\n",
+            star(1),
+            star(2)
+        ));
+    }
+    table
+}
+
+/// The time shown for a solved part.
+#[derive(Clone, Copy)]
+struct Timing {
+    elapsed: Duration,
+    /// Whether this is a benchmark median, rather than the time of a single run.
+    median: bool,
+}
+
+/// The benchmark median of a solved part if there is one, or else the time of its run in
+/// `results`.
+fn timing(
+    results: &[PartResult],
+    medians: &BTreeMap<(u8, u8), Duration>,
+    day: u8,
+    part: u8,
+) -> Option<Timing> {
+    let result =
+        results_for(results, day).find(|r| r.part == part && r.status == Status::Solved)?;
+    Some(match medians.get(&(day, part)) {
+        Some(&median) => Timing {
+            elapsed: median,
+            median: true,
+        },
+        None => Timing {
+            elapsed: result.elapsed,
+            median: false,
+        },
+    })
+}
+
+fn format_timing(timing: Option<Timing>) -> String {
+    match timing {
+        Some(Timing {
+            elapsed,
+            median: true,
+        }) => format!("`{elapsed:.1?}`"),
+        Some(Timing { elapsed, .. }) => format!("`{elapsed:.1?}`*"),
+        None => String::from("-"),
+    }
+}
+
+/// Lists the time of every solved part. `medians` come from `--bench` runs, by day and
+/// part; parts without one show the time of their run in `results`, marked with `*`.
+pub fn benchmark_table(
+    results: &[PartResult],
+    medians: &BTreeMap<(u8, u8), Duration>,
+    year: i16,
+) -> String {
+    let mut table =
+        String::from("## Benchmarks\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n");
+    let mut timings = vec![];
+    for day in solved_days(results) {
+        let part_one = timing(results, medians, day, 1);
+        let part_two = timing(results, medians, day, 2);
+        table.push_str(&format!(
+            "| [Day {day}](./src/days/y{year}/day{day:02}.rs) | {} | {} |\n",
+            format_timing(part_one),
+            format_timing(part_two)
+        ));
+        timings.extend(part_one.into_iter().chain(part_two));
+    }
+
+    let total: Duration = timings.iter().map(|t| t.elapsed).sum();
+    table.push_str(&format!(
+        "\n**Total: {:.2}ms**\n",
+        total.as_secs_f64() * 1000_f64
+    ));
+    let note = match (
+        timings.iter().any(|t| t.median),
+        timings.iter().any(|t| !t.median),
+    ) {
+        (true, false) => "Medians of the latest `--bench` run of each part.",
+        (true, true) => {
+            "Medians of the latest `--bench` run of each part. Parts marked with `*` were not benchmarked, they show the time of a single run."
+        }
+        _ => "Times of a single run. Run `cargo all --bench` first to show medians instead.",
+    };
+    table.push_str(&format!("\n_{note}_\n"));
+    table
+}
+
+/// Replaces everything between the first two occurrences of `marker` with `content`.
+pub fn replace_section(readme: &str, marker: &str, content: &str) -> Result<String, String> {
+    let missing = || format!("README is missing a pair of \"{marker}\" markers");
+    let start = readme.find(marker).ok_or_else(missing)? + marker.len();
+    let end = start + readme[start..].find(marker).ok_or_else(missing)?;
+
+    Ok(format!(
+        "{}\n{}{}",
+        &readme[..start],
+        content,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn result(day: u8, part: u8, status: Status, micros: u64) -> PartResult {
+        PartResult {
+            day,
+            part,
+            answer: None,
+            status,
+            elapsed: Duration::from_micros(micros),
+        }
+    }
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            result(1, 1, Status::Solved, 70),
+            result(1, 2, Status::Solved, 1460),
+            result(16, 1, Status::Solved, 300),
+            result(16, 2, Status::NotSolved, 0),
+            result(19, 1, Status::Panicked(String::from("oops")), 10),
+            result(19, 2, Status::NotSolved, 0),
+        ]
+    }
+
+    #[test]
+    fn test_stars_table() {
+        assert_eq!(
+            stars_table(&results(), 2022),
+            indoc! {"
+                ## 2022 Results
+
+                | Day | Part 1 | Part 2 |
+                | :---: | :---: | :---: |
+                | [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |
+                | [Day 16](https://adventofcode.com/2022/day/16) | ⭐ |   |
+            "}
+        );
+    }
+
+    #[test]
+    fn test_benchmark_table() {
+        assert_eq!(
+            benchmark_table(&results(), &BTreeMap::new(), 2022),
+            indoc! {"
+                ## Benchmarks
+
+                | Day | Part 1 | Part 2 |
+                | :---: | :---: | :---: |
+                | [Day 1](./src/days/y2022/day01.rs) | `70.0µs`* | `1.5ms`* |
+                | [Day 16](./src/days/y2022/day16.rs) | `300.0µs`* | - |
+
+                **Total: 1.83ms**
+
+                _Times of a single run. Run `cargo all --bench` first to show medians instead._
+            "}
+        );
+
+        // parts that are not solved in this run are left out, even if benchmarked.
+        let medians = BTreeMap::from([
+            ((1, 1), Duration::from_micros(50)),
+            ((1, 2), Duration::from_micros(1000)),
+            ((16, 1), Duration::from_micros(200)),
+            ((16, 2), Duration::from_micros(900)),
+        ]);
+        let table = benchmark_table(&results(), &medians, 2022);
+        assert!(table.contains("| [Day 1](./src/days/y2022/day01.rs) | `50.0µs` | `1.0ms` |\n"));
+        assert!(table.contains("| [Day 16](./src/days/y2022/day16.rs) | `200.0µs` | - |\n"));
+        assert!(table.contains("**Total: 1.25ms**"));
+        assert!(table.contains("_Medians of the latest `--bench` run of each part._"));
+
+        let medians = BTreeMap::from([((1, 1), Duration::from_micros(50))]);
+        let table = benchmark_table(&results(), &medians, 2022);
+        assert!(table.contains("| `50.0µs` | `1.5ms`* |"));
+        assert!(table.contains("Parts marked with `*` were not benchmarked"));
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let readme = "intro\n<!--- m --->\nold\n<!--- m --->\noutro\n";
+        assert_eq!(
+            replace_section(readme, "<!--- m --->", "new\n"),
+            Ok(String::from(
+                "intro\n<!--- m --->\nnew\n<!--- m --->\noutro\n"
+            ))
+        );
+        assert!(replace_section("intro\n<!--- m --->\n", "<!--- m --->", "new\n").is_err());
+    }
+}
@@ -0,0 +1,663 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Display;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{self, Command, Stdio};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error;
+use crate::params::Params;
+use crate::solution::DynSolution;
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    NotSolved,
+    /// The solver returned an error, e.g. on malformed input.
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::NotSolved => "not_solved",
+            Status::Failed(_) => "failed",
+            Status::Panicked(_) => "panicked",
+            Status::TimedOut => "timed_out",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    pub fn not_solved(day: u8, part: u8) -> Self {
+        PartResult {
+            day,
+            part,
+            answer: None,
+            status: Status::NotSolved,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn failed(day: u8, part: u8, message: String) -> Self {
+        PartResult {
+            status: Status::Failed(message),
+            ..PartResult::not_solved(day, part)
+        }
+    }
+}
+
+/// Runs one part of a solution, timing it and turning an error into [`Status::Failed`]
+/// and a panic into [`Status::Panicked`].
+pub fn run_part(solution: &dyn DynSolution, part: u8, input: &str, params: &Params) -> PartResult {
+    run_fn(solution.day(), part, || solution.solve(part, input, params))
+}
+
+/// Like [`run_part`], for a part given as a function returning its answer.
+pub fn run_fn(
+    day: u8,
+    part: u8,
+    solve: impl FnOnce() -> error::Result<Option<String>>,
+) -> PartResult {
+    let timer = Instant::now();
+    let result = catch_silently(solve);
+    let elapsed = timer.elapsed();
+
+    let (answer, status) = match result {
+        Ok(Ok(Some(answer))) => (Some(answer), Status::Solved),
+        Ok(Ok(None)) => (None, Status::NotSolved),
+        Ok(Err(e)) => (None, Status::Failed(e.to_string())),
+        Err(payload) => (None, Status::Panicked(panic_message(payload.as_ref()))),
+    };
+    PartResult {
+        day,
+        part,
+        answer,
+        status,
+        elapsed,
+    }
+}
+
+thread_local! {
+    static SILENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Like `catch_unwind`, without the default panic hook printing the panic to stderr, where
+/// it would interleave with the output of other parts running at the same time. The
+/// message is reported as [`Status::Panicked`] instead.
+fn catch_silently<T>(f: impl FnOnce() -> T) -> thread::Result<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENT.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+
+    let silent = SILENT.with(|silent| silent.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    SILENT.with(|s| s.set(silent));
+    result
+}
+
+pub fn print_part(result: &PartResult) {
+    println!("🎄 {ANSI_BOLD}Part {}{ANSI_RESET} 🎄", result.part);
+    match (&result.status, &result.answer) {
+        (Status::Solved, Some(answer)) => println!(
+            "{answer} {ANSI_ITALIC}(elapsed: {:.2?}){ANSI_RESET}",
+            result.elapsed
+        ),
+        (Status::Failed(message), _) => println!("error: {message}"),
+        (Status::Panicked(message), _) => println!("panicked: {message}"),
+        (Status::TimedOut, _) => println!("timed out after {:.2?}.", result.elapsed),
+        _ => println!("not solved."),
+    }
+}
+
+/// Flag that makes the runner binary solve a single part for [`run_part_isolated`].
+pub const CHILD_FLAG: &str = "--child-part";
+
+/// Exit code of a child whose solver returned an error, which it prints to stderr on a
+/// line starting with [`RESULT_MARKER`].
+const CHILD_ERROR_CODE: i32 = 2;
+
+/// Prefix of the line on which a child reports its result on stdout, or its error on
+/// stderr. Anything the solver prints before it is ignored.
+const RESULT_MARKER: &str = "\u{1e}advent_of_code result:";
+
+/// Child side of [`run_part_isolated`]: solves the part and prints its result on a line of
+/// its own after whatever the solver printed. Panics are left to terminate the process.
+pub fn run_child(solution: &dyn DynSolution, part: u8, input: &str, params: &Params) {
+    let timer = Instant::now();
+    let answer = solution.solve(part, input, params);
+    let elapsed = timer.elapsed();
+
+    match answer {
+        Ok(answer) => println!("\n{}", result_line(elapsed, answer.as_deref())),
+        Err(e) => {
+            eprintln!("\n{RESULT_MARKER}{}", escape(&e.to_string()));
+            process::exit(CHILD_ERROR_CODE);
+        }
+    }
+}
+
+/// The marker, the elapsed nanoseconds and the answer, if there is one, with newlines
+/// escaped so multi-line answers fit on the line.
+fn result_line(elapsed: Duration, answer: Option<&str>) -> String {
+    let mut line = format!("{RESULT_MARKER}{}", elapsed.as_nanos());
+    if let Some(answer) = answer {
+        line.push(' ');
+        line.push_str(&escape(answer));
+    }
+    line
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// The last line starting with [`RESULT_MARKER`] in `output`, without the marker.
+fn marked_line(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix(RESULT_MARKER))
+}
+
+/// Reads back the last result line in the output of a child, see [`result_line`].
+fn parse_result_line(stdout: &str) -> Option<(Duration, Option<String>)> {
+    let line = marked_line(stdout)?;
+    let (elapsed, answer) = match line.split_once(' ') {
+        Some((elapsed, answer)) => (elapsed, Some(answer)),
+        None => (line, None),
+    };
+    let elapsed = Duration::from_nanos(elapsed.parse().ok()?);
+
+    Some((elapsed, answer.map(unescape)))
+}
+
+/// Runs one part in a child process of the current executable, killing it after `timeout`.
+/// Unlike [`run_part`], this also survives solvers that hang or abort the process.
+/// `overrides` are passed on as `--name value` arguments.
+pub fn run_part_isolated(
+    year: i16,
+    day: u8,
+    part: u8,
+    overrides: &[(String, String)],
+    timeout: Duration,
+) -> PartResult {
+    let result = |answer, status, elapsed| PartResult {
+        day,
+        part,
+        answer,
+        status,
+        elapsed,
+    };
+    let spawned = env::current_exe().and_then(|exe| {
+        Command::new(exe)
+            .args([
+                day.to_string(),
+                String::from("--year"),
+                year.to_string(),
+                String::from(CHILD_FLAG),
+                part.to_string(),
+            ])
+            .args(
+                overrides
+                    .iter()
+                    .flat_map(|(name, value)| [format!("--{name}"), value.clone()]),
+            )
+            .env("RUST_BACKTRACE", "0")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+    });
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            let message = format!("could not spawn child process: {e}");
+            return result(None, Status::Failed(message), Duration::ZERO);
+        }
+    };
+
+    // drain the pipes while waiting, so a chatty solver cannot block on a full pipe.
+    let read_pipe = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut out = String::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_string(&mut out);
+            }
+            out
+        })
+    };
+    let stdout = read_pipe(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr = read_pipe(child.stderr.take().map(|p| Box::new(p) as _));
+
+    let timer = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if timer.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            Ok(None) => {
+                thread::sleep(Duration::from_millis(5).min(timeout.saturating_sub(timer.elapsed())))
+            }
+            Err(e) => {
+                let message = format!("could not wait for child process: {e}");
+                return result(None, Status::Failed(message), timer.elapsed());
+            }
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    match status {
+        None => result(None, Status::TimedOut, timeout),
+        Some(status) if status.success() => match parse_result_line(&stdout) {
+            Some((elapsed, Some(answer))) => result(Some(answer), Status::Solved, elapsed),
+            Some((elapsed, None)) => result(None, Status::NotSolved, elapsed),
+            None => {
+                let message = String::from("child process exited without a result");
+                result(None, Status::Panicked(message), timer.elapsed())
+            }
+        },
+        Some(status) if status.code() == Some(CHILD_ERROR_CODE) => {
+            let message = match marked_line(&stderr) {
+                Some(message) => unescape(message),
+                None => child_output(&stderr),
+            };
+            result(None, Status::Failed(message), timer.elapsed())
+        }
+        Some(status) => {
+            let message = child_panic_message(&stderr, status);
+            result(None, Status::Panicked(message), timer.elapsed())
+        }
+    }
+}
+
+/// Extracts the message from the default panic hook output, which looks like
+/// `thread 'main' panicked at src/days/y2022/day16.rs:21:10:\n<message>\nnote: ...`.
+fn child_panic_message(stderr: &str, status: impl Display) -> String {
+    let message: Vec<&str> = stderr
+        .lines()
+        .skip_while(|line| !line.contains("panicked at"))
+        .skip(1)
+        .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace:"))
+        .collect();
+
+    if !message.is_empty() {
+        message.join("\n")
+    } else if !child_output(stderr).is_empty() {
+        format!("{status}: {}", child_output(stderr))
+    } else {
+        status.to_string()
+    }
+}
+
+/// The stderr of a child, without the warnings about its input that the parent already
+/// printed when loading the day.
+fn child_output(stderr: &str) -> String {
+    stderr
+        .lines()
+        .filter(|line| !line.starts_with("Warning: input of day"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic payload")
+    }
+}
+
+/// Maps `f` over `items` on up to `jobs` threads, handing each result to `emit` in the
+/// order of `items` as soon as it and all results before it are done.
+pub fn run_ordered<I, T>(
+    items: &[I],
+    jobs: usize,
+    f: impl Fn(&I) -> T + Sync,
+    mut emit: impl FnMut(&I, T),
+) where
+    I: Sync,
+    T: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                match items.get(index) {
+                    Some(item) => {
+                        if sender.send((index, f(item))).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&printed) {
+                emit(&items[printed], result);
+                printed += 1;
+            }
+        }
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format \"{s}\", expected human, json or csv"
+            )),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option(s: Option<&str>) -> String {
+    s.map(json_string).unwrap_or_else(|| String::from("null"))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn panic_of(status: &Status) -> Option<&str> {
+    match status {
+        Status::Panicked(message) => Some(message),
+        _ => None,
+    }
+}
+
+fn error_of(status: &Status) -> Option<&str> {
+    match status {
+        Status::Failed(message) => Some(message),
+        _ => None,
+    }
+}
+
+pub fn to_json(results: &[PartResult]) -> String {
+    let rows: Vec<String> = results
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"status\": {}, \"answer\": {}, \"elapsed_ns\": {}, \"panic\": {}, \"error\": {}}}",
+                r.day,
+                r.part,
+                json_string(r.status.name()),
+                json_option(r.answer.as_deref()),
+                r.elapsed.as_nanos(),
+                json_option(panic_of(&r.status)),
+                json_option(error_of(&r.status)),
+            )
+        })
+        .collect();
+    format!("[\n{}\n]", rows.join(",\n"))
+}
+
+pub fn to_csv(results: &[PartResult]) -> String {
+    let mut out = String::from("day,part,status,answer,elapsed_ns,panic,error\n");
+    for r in results {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            r.day,
+            r.part,
+            r.status.name(),
+            csv_field(r.answer.as_deref().unwrap_or_default()),
+            r.elapsed.as_nanos(),
+            csv_field(panic_of(&r.status).unwrap_or_default()),
+            csv_field(error_of(&r.status).unwrap_or_default()),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_panic_message() {
+        let status = "exit status: 101";
+        assert_eq!(
+            child_panic_message(
+                "thread 'main' panicked at src/days/y2022/day13.rs:45:10:\nunexpected token\nnote: run with `RUST_BACKTRACE=1`\n",
+                status
+            ),
+            "unexpected token"
+        );
+        assert_eq!(child_panic_message("", status), "exit status: 101");
+        assert_eq!(
+            child_panic_message(
+                "Warning: input of day 16 does not end with a newline (normalized).\nfatal runtime error: stack overflow\n",
+                "signal: 6 (SIGABRT)"
+            ),
+            "signal: 6 (SIGABRT): fatal runtime error: stack overflow"
+        );
+    }
+
+    #[test]
+    fn test_run_fn() {
+        let result = run_fn(1, 2, || Ok(Some(String::from("24000"))));
+        assert_eq!(result.answer.as_deref(), Some("24000"));
+        assert_eq!(result.status, Status::Solved);
+
+        let result = run_fn(1, 2, || Err(error::Error::new("oops").at_line(3)));
+        assert_eq!(result.status, Status::Failed(String::from("line 3: oops")));
+
+        let result = run_fn(1, 2, || panic!("unexpected {}", "token"));
+        assert_eq!(
+            result.status,
+            Status::Panicked(String::from("unexpected token"))
+        );
+        assert!(!SILENT.with(Cell::get));
+    }
+
+    #[test]
+    fn test_parse_result_line() {
+        // what a child prints for a solver that prints debug output of its own.
+        let stdout = |answer| {
+            format!(
+                "42\nMinute 3\nno newline\n{}\n",
+                result_line(Duration::from_nanos(1500), answer)
+            )
+        };
+        assert_eq!(
+            parse_result_line(&stdout(Some("1651"))),
+            Some((Duration::from_nanos(1500), Some(String::from("1651"))))
+        );
+        assert_eq!(
+            parse_result_line(&stdout(Some("#.\\n\n.#\r"))),
+            Some((
+                Duration::from_nanos(1500),
+                Some(String::from("#.\\n\n.#\r"))
+            ))
+        );
+        assert_eq!(
+            parse_result_line(&stdout(None)),
+            Some((Duration::from_nanos(1500), None))
+        );
+        assert_eq!(parse_result_line("42\n1651\n"), None);
+    }
+
+    #[test]
+    fn test_run_ordered() {
+        let items: Vec<u64> = (1..=25).collect();
+        let mut emitted = vec![];
+        run_ordered(
+            &items,
+            4,
+            |&item| {
+                thread::sleep(Duration::from_millis(26 - item));
+                item * 2
+            },
+            |&item, result| emitted.push((item, result)),
+        );
+        assert_eq!(
+            emitted,
+            items.iter().map(|&i| (i, i * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 10,
+                part: 2,
+                answer: Some(String::from("#.\n.#")),
+                status: Status::Solved,
+                elapsed: Duration::from_micros(3),
+            },
+            PartResult {
+                day: 16,
+                part: 1,
+                answer: None,
+                status: Status::Panicked(String::from("called `Option::unwrap()`, \"oops\"")),
+                elapsed: Duration::from_nanos(12),
+            },
+            PartResult::not_solved(19, 1),
+            PartResult {
+                day: 21,
+                part: 1,
+                answer: None,
+                status: Status::Failed(String::from("line 3: unknown operation \"%\"")),
+                elapsed: Duration::from_nanos(5),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&results()),
+            [
+                "[",
+                r##"  {"day": 10, "part": 2, "status": "solved", "answer": "#.\n.#", "elapsed_ns": 3000, "panic": null, "error": null},"##,
+                r##"  {"day": 16, "part": 1, "status": "panicked", "answer": null, "elapsed_ns": 12, "panic": "called `Option::unwrap()`, \"oops\"", "error": null},"##,
+                r##"  {"day": 19, "part": 1, "status": "not_solved", "answer": null, "elapsed_ns": 0, "panic": null, "error": null},"##,
+                r##"  {"day": 21, "part": 1, "status": "failed", "answer": null, "elapsed_ns": 5, "panic": null, "error": "line 3: unknown operation \"%\""}"##,
+                "]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            json_string("tab\t, bell\u{7} and \\"),
+            r#""tab\t, bell\u0007 and \\""#
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&results()),
+            [
+                "day,part,status,answer,elapsed_ns,panic,error",
+                "10,2,solved,\"#.\n.#\",3000,,",
+                "16,1,panicked,,12,\"called `Option::unwrap()`, \"\"oops\"\"\",",
+                "19,1,not_solved,,0,,",
+                "21,1,failed,,5,,\"line 3: unknown operation \"\"%\"\"\"",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::Answers;
+use crate::YEAR;
+
+/// Which days and parts a run covers. Shared by all modes of the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub year: i16,
+    pub days: Vec<u8>,
+    pub parts: Vec<u8>,
+    /// `Some(true)` keeps only parts with a recorded answer, `Some(false)` only parts without.
+    pub answered: Option<bool>,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Selection {
+            year: YEAR,
+            days: (1..=25).collect(),
+            parts: vec![1, 2],
+            answered: None,
+        }
+    }
+}
+
+impl Selection {
+    /// Reads `--year <year>`, `--days <list>`, `--part <n>`, `--only-solved` and
+    /// `--only-unsolved`.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let only_solved = args.contains("--only-solved");
+        let only_unsolved = args.contains("--only-unsolved");
+        let answered = match (only_solved, only_unsolved) {
+            (true, true) => {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: String::from("--only-solved and --only-unsolved are exclusive"),
+                })
+            }
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        };
+
+        let default = Selection::default();
+        Ok(Selection {
+            year: args
+                .opt_value_from_str(["-y", "--year"])?
+                .unwrap_or(default.year),
+            days: args
+                .opt_value_from_fn("--days", parse_days)?
+                .unwrap_or(default.days),
+            parts: args
+                .opt_value_from_fn("--part", parse_part)?
+                .map(|part| vec![part])
+                .unwrap_or(default.parts),
+            answered,
+        })
+    }
+
+    /// The selected parts of `day`, after applying the answer filter.
+    pub fn parts(&self, day: u8) -> Vec<u8> {
+        if !self.days.contains(&day) {
+            return vec![];
+        }
+        let answers = match self.answered {
+            Some(_) => Answers::read(self.year, day).unwrap_or_default(),
+            None => Answers::default(),
+        };
+        self.parts
+            .iter()
+            .copied()
+            .filter(|&part| match self.answered {
+                Some(answered) => answers.get(part).is_some() == answered,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Every selected day that has at least one selected part, with those parts.
+    pub fn plan(&self) -> Vec<(u8, Vec<u8>)> {
+        self.days
+            .iter()
+            .map(|&day| (day, self.parts(day)))
+            .filter(|(_, parts)| !parts.is_empty())
+            .collect()
+    }
+}
+
+/// Parses a single day, which must be in 1–25.
+pub fn parse_day(input: &str) -> Result<u8, String> {
+    match input.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day \"{input}\", expected a number from 1 to 25"
+        )),
+    }
+}
+
+/// Parses a comma-separated list of days and day ranges, e.g. `1-10,17`.
+pub fn parse_days(input: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+    for item in input.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_day(from)?, parse_day(to)?);
+                if from > to {
+                    return Err(format!("invalid day range \"{item}\""));
+                }
+                days.extend(from..=to);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+fn parse_part(input: &str) -> Result<u8, String> {
+    match input {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part \"{input}\", expected 1 or 2")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("17"), Ok(vec![17]));
+        assert_eq!(parse_days("1-3,17"), Ok(vec![1, 2, 3, 17]));
+        assert_eq!(parse_days("17, 3-4,3"), Ok(vec![3, 4, 17]));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("10-1").is_err());
+        assert!(parse_days("one").is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        let selection = Selection {
+            year: 2022,
+            days: vec![1, 17],
+            parts: vec![2],
+            answered: None,
+        };
+        assert_eq!(selection.plan(), vec![(1, vec![2]), (17, vec![2])]);
+        assert_eq!(selection.parts(3), Vec::<u8>::new());
+    }
+}
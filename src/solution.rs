@@ -0,0 +1,164 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::params::{Param, Params};
+
+/// A puzzle solution for a single day.
+///
+/// `parse` runs once per input and its result is shared by both parts. Days that parse
+/// differently per part can use `&str` as their `Input` and do the work in the parts.
+///
+/// Values that are not part of the input but differ between the example and the real
+/// input are declared in `PARAMS`, and handed to both parts. Parts fail with an
+/// [`Error`](crate::error::Error) on malformed input instead of panicking.
+pub trait Solution {
+    const DAY: u8;
+    const PARAMS: &'static [Param] = &[];
+    /// Whether `parse` does any work. Benchmarks only time the parse step when it does.
+    const PARSES: bool = true;
+
+    type Input<'a>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Self::Input<'_>;
+    fn part_one(input: &Self::Input<'_>, params: &Params) -> Result<Option<Self::PartOne>>;
+    fn part_two(input: &Self::Input<'_>, params: &Params) -> Result<Option<Self::PartTwo>>;
+}
+
+/// Type-erased view of a [`Solution`], so days with different answer types can live in
+/// the same registry. See [`crate::days`].
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn params(&self) -> &'static [Param];
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<Option<String>>;
+    fn parses(&self) -> bool;
+
+    /// Times a single call to `parse`.
+    fn time_parse(&self, input: &str) -> Duration;
+
+    /// Times a single call to a part, excluding the time spent in `parse`.
+    fn time_part(&self, part: u8, input: &str, params: &Params) -> Duration;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<Option<String>> {
+        let input = S::parse(input);
+        Ok(match part {
+            1 => S::part_one(&input, params)?.map(|answer| answer.to_string()),
+            2 => S::part_two(&input, params)?.map(|answer| answer.to_string()),
+            _ => None,
+        })
+    }
+
+    fn parses(&self) -> bool {
+        S::PARSES
+    }
+
+    fn time_parse(&self, input: &str) -> Duration {
+        let timer = Instant::now();
+        black_box(S::parse(black_box(input)));
+        timer.elapsed()
+    }
+
+    fn time_part(&self, part: u8, input: &str, params: &Params) -> Duration {
+        let input = S::parse(input);
+        let timer = Instant::now();
+        match part {
+            1 => drop(black_box(S::part_one(black_box(&input), params))),
+            2 => drop(black_box(S::part_two(black_box(&input), params))),
+            _ => {}
+        }
+        timer.elapsed()
+    }
+}
+
+/// Implements [`Solution`] for a day module that exposes `part_one` and `part_two`
+/// taking the raw input. Such days do all of their work in the parts.
+///
+/// `solution!(Day01, 1, u32, u32)` declares `pub struct Day01` for day 1, with `u32`
+/// answers for both parts.
+///
+/// The parts return `Option<T>`, or `Result<T, E>` / `Result<Option<T>, E>` if they can
+/// fail, with any `E` that converts into [`Error`](crate::error::Error).
+///
+/// `solution!(Day15, 15, usize, isize, PARAMS)` additionally declares the parameters of
+/// the day, and hands them to `part_one(input, params)` and `part_two(input, params)`.
+#[macro_export]
+macro_rules! solution {
+    ($name:ident, $day:expr, $part_one:ty, $part_two:ty) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            const DAY: u8 = $day;
+            const PARSES: bool = false;
+
+            type Input<'a> = &'a str;
+            type PartOne = $part_one;
+            type PartTwo = $part_two;
+
+            fn parse(input: &str) -> Self::Input<'_> {
+                input
+            }
+
+            fn part_one(
+                input: &Self::Input<'_>,
+                _params: &$crate::params::Params,
+            ) -> $crate::error::Result<Option<Self::PartOne>> {
+                $crate::error::IntoAnswer::into_answer(part_one(input))
+            }
+
+            fn part_two(
+                input: &Self::Input<'_>,
+                _params: &$crate::params::Params,
+            ) -> $crate::error::Result<Option<Self::PartTwo>> {
+                $crate::error::IntoAnswer::into_answer(part_two(input))
+            }
+        }
+    };
+    ($name:ident, $day:expr, $part_one:ty, $part_two:ty, $params:expr) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            const DAY: u8 = $day;
+            const PARSES: bool = false;
+            const PARAMS: &'static [$crate::params::Param] = $params;
+
+            type Input<'a> = &'a str;
+            type PartOne = $part_one;
+            type PartTwo = $part_two;
+
+            fn parse(input: &str) -> Self::Input<'_> {
+                input
+            }
+
+            fn part_one(
+                input: &Self::Input<'_>,
+                params: &$crate::params::Params,
+            ) -> $crate::error::Result<Option<Self::PartOne>> {
+                $crate::error::IntoAnswer::into_answer(part_one(input, params))
+            }
+
+            fn part_two(
+                input: &Self::Input<'_>,
+                params: &$crate::params::Params,
+            ) -> $crate::error::Result<Option<Self::PartTwo>> {
+                $crate::error::IntoAnswer::into_answer(part_two(input, params))
+            }
+        }
+    };
+}
@@ -1,12 +1,29 @@
-use std::cmp::Ordering::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 use std::{collections::BTreeMap, fmt::Display};
 
 use num_integer::Integer;
 use num_iter::range_inclusive;
 use num_traits::{ToPrimitive, Zero};
 
+/// A rule giving the value of cells that are not stored.
+enum DefaultRule<N, T> {
+    Rectangle(RangeInclusive<(N, N)>, T),
+    Function(Box<dyn Fn((N, N)) -> Option<T> + Send + Sync>),
+}
+
+/// A grid that only stores the cells that were inserted. Every other cell has a default
+/// value, resolved in this order:
+///
+/// 1. rectangle and function rules, the most recently added first
+/// 2. the default of the cell's row
+/// 3. the default of the cell's column
+/// 4. the default passed to the constructor
+///
+/// [`SparseTable::get`] skips function rules, which compute their values;
+/// [`SparseTable::resolve`] applies them too.
 pub struct SparseTable<N, T>
 where
     T: Clone,
@@ -15,6 +32,9 @@ where
     data: BTreeMap<(N, N), T>,
     default: T,
     row_defaults: HashMap<N, T>,
+    col_defaults: HashMap<N, T>,
+    default_rules: Vec<DefaultRule<N, T>>,
+    start: (N, N),
     row_min: N,
     row_max: N,
     col_min: N,
@@ -29,10 +49,14 @@ impl<N: Integer + Hash + Copy, T: Clone> SparseTable<N, T> {
     pub fn new_with_start_point(default: T, (row, col): (N, N)) -> Self {
         let data = BTreeMap::new();
         let row_defaults = HashMap::new();
+        let col_defaults = HashMap::new();
         SparseTable {
             data,
             default,
             row_defaults,
+            col_defaults,
+            default_rules: Vec::new(),
+            start: (row, col),
             row_min: row,
             row_max: row,
             col_min: col,
@@ -57,14 +81,144 @@ impl<N: Integer + Hash + Copy, T: Clone> SparseTable<N, T> {
         self.data.entry((row, col)).or_insert(value)
     }
 
+    /// Removes a stored cell, after which `get` returns the default again. The bounds
+    /// shrink to what is still stored, but never past the start point, a row or column
+    /// default or a rectangle rule.
+    pub fn remove(&mut self, (row, col): (N, N)) -> Option<T> {
+        let removed = self.data.remove(&(row, col));
+        let on_bounds = row == self.row_min
+            || row == self.row_max
+            || col == self.col_min
+            || col == self.col_max;
+        if removed.is_some() && on_bounds {
+            self.recompute_bounds();
+        }
+        removed
+    }
+
+    fn recompute_bounds(&mut self) {
+        let (start_row, start_col) = self.start;
+        let corners = || {
+            self.default_rules.iter().flat_map(|rule| match rule {
+                DefaultRule::Rectangle(rectangle, _) => vec![*rectangle.start(), *rectangle.end()],
+                DefaultRule::Function(_) => vec![],
+            })
+        };
+        let rows = || {
+            self.data
+                .keys()
+                .copied()
+                .chain(corners())
+                .map(|(row, _)| row)
+                .chain(self.row_defaults.keys().copied())
+                .chain([start_row])
+        };
+        let cols = || {
+            self.data
+                .keys()
+                .copied()
+                .chain(corners())
+                .map(|(_, col)| col)
+                .chain(self.col_defaults.keys().copied())
+                .chain([start_col])
+        };
+        let (row_min, row_max) = (rows().min().unwrap(), rows().max().unwrap());
+        let (col_min, col_max) = (cols().min().unwrap(), cols().max().unwrap());
+        self.row_min = row_min;
+        self.row_max = row_max;
+        self.col_min = col_min;
+        self.col_max = col_max;
+    }
+
+    /// Whether a value is stored for the cell, as opposed to it falling back to a default.
+    pub fn contains(&self, (row, col): (N, N)) -> bool {
+        self.data.contains_key(&(row, col))
+    }
+
+    /// The number of stored cells.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The stored cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((N, N), &T)> {
+        self.data.iter().map(|(&key, value)| (key, value))
+    }
+
+    /// The stored cells of a row, from left to right.
+    pub fn iter_row(&self, row: N) -> impl Iterator<Item = ((N, N), &T)> {
+        self.range((row, self.col_min)..=(row, self.col_max))
+    }
+
+    /// The stored cells of a column, from top to bottom. Unlike rows, this visits every
+    /// stored cell.
+    pub fn iter_col(&self, col: N) -> impl Iterator<Item = ((N, N), &T)> {
+        self.iter().filter(move |&((_, c), _)| c == col)
+    }
+
+    /// The stored cells in the rectangle spanned by two corners, row by row. Only rows
+    /// inside the rectangle are visited.
+    pub fn range(&self, range: RangeInclusive<(N, N)>) -> impl Iterator<Item = ((N, N), &T)> {
+        let ((row_start, col_start), (row_end, col_end)) = range.into_inner();
+        let rows = (row_start <= row_end && col_start <= col_end)
+            .then(|| self.data.range((row_start, col_start)..=(row_end, col_end)));
+        rows.into_iter()
+            .flatten()
+            .filter(move |&(&(_, col), _)| col_start <= col && col <= col_end)
+            .map(|(&key, value)| (key, value))
+    }
+
+    /// The value of a cell, without applying function rules.
     pub fn get(&self, (row, col): (N, N)) -> &T {
-        self.data
-            .get(&(row, col))
-            .unwrap_or_else(|| self.get_default_for_row(row))
+        self.data.get(&(row, col)).unwrap_or_else(|| {
+            self.default_rules
+                .iter()
+                .rev()
+                .find_map(|rule| match rule {
+                    DefaultRule::Rectangle(rectangle, value) => {
+                        in_rectangle(rectangle, (row, col)).then_some(value)
+                    }
+                    DefaultRule::Function(_) => None,
+                })
+                .unwrap_or_else(|| self.get_constant_default((row, col)))
+        })
     }
 
-    fn get_default_for_row(&self, row: N) -> &T {
-        self.row_defaults.get(&row).unwrap_or(&self.default)
+    /// The value of a cell, applying function rules too. Only owned if it comes from one.
+    pub fn resolve(&self, (row, col): (N, N)) -> Cow<'_, T> {
+        match self.data.get(&(row, col)) {
+            Some(value) => Cow::Borrowed(value),
+            None => self.resolve_default((row, col)),
+        }
+    }
+
+    fn resolve_default(&self, (row, col): (N, N)) -> Cow<'_, T> {
+        for rule in self.default_rules.iter().rev() {
+            match rule {
+                DefaultRule::Rectangle(rectangle, value) => {
+                    if in_rectangle(rectangle, (row, col)) {
+                        return Cow::Borrowed(value);
+                    }
+                }
+                DefaultRule::Function(function) => {
+                    if let Some(value) = function((row, col)) {
+                        return Cow::Owned(value);
+                    }
+                }
+            }
+        }
+        Cow::Borrowed(self.get_constant_default((row, col)))
+    }
+
+    fn get_constant_default(&self, (row, col): (N, N)) -> &T {
+        self.row_defaults
+            .get(&row)
+            .or_else(|| self.col_defaults.get(&col))
+            .unwrap_or(&self.default)
     }
 
     pub fn add_row_default(&mut self, row: N, default: T) -> Option<T> {
@@ -72,6 +226,30 @@ impl<N: Integer + Hash + Copy, T: Clone> SparseTable<N, T> {
         self.row_defaults.insert(row, default)
     }
 
+    pub fn add_col_default(&mut self, col: N, default: T) -> Option<T> {
+        self.update_min_max_vals(self.row_min, col);
+        self.col_defaults.insert(col, default)
+    }
+
+    /// Gives every cell in the rectangle spanned by two corners the value `default`,
+    /// such as a wall. The bounds grow to include the rectangle.
+    pub fn add_rectangle_default(&mut self, rectangle: RangeInclusive<(N, N)>, default: T) {
+        let (row_start, col_start) = *rectangle.start();
+        let (row_end, col_end) = *rectangle.end();
+        self.update_min_max_vals(row_start, col_start);
+        self.update_min_max_vals(row_end, col_end);
+        self.default_rules
+            .push(DefaultRule::Rectangle(rectangle, default));
+    }
+
+    /// Gives cells the value returned by `rule`, unless it returns `None`. Suited for
+    /// rules that cover infinitely many cells, such as "every row from 12 on is rock",
+    /// which is why the bounds do not change.
+    pub fn add_default_rule(&mut self, rule: impl Fn((N, N)) -> Option<T> + Send + Sync + 'static) {
+        self.default_rules
+            .push(DefaultRule::Function(Box::new(rule)));
+    }
+
     pub fn col_min(&self) -> &N {
         &self.col_min
     }
@@ -89,59 +267,132 @@ impl<N: Integer + Hash + Copy, T: Clone> SparseTable<N, T> {
     }
 }
 
-impl<N, T> Display for SparseTable<N, T>
+fn in_rectangle<N: Integer + Copy>(rectangle: &RangeInclusive<(N, N)>, (row, col): (N, N)) -> bool {
+    let (row_start, col_start) = *rectangle.start();
+    let (row_end, col_end) = *rectangle.end();
+    row_start <= row && row <= row_end && col_start <= col && col <= col_end
+}
+
+/// Renders a rectangle of a [`SparseTable`], created by [`SparseTable::render`].
+/// By default this shows the whole table with a column ruler and row numbers.
+pub struct Render<'a, N, T, F>
+where
+    T: Clone,
+    N: Integer + Hash,
+{
+    table: &'a SparseTable<N, T>,
+    format: F,
+    viewport: RangeInclusive<(N, N)>,
+    labels: bool,
+    ticks: usize,
+    overlays: HashMap<(N, N), String>,
+}
+
+impl<N: Integer + Hash + Copy, T: Clone> SparseTable<N, T> {
+    /// Renders the table with every cell written by `format`, which lets tables of
+    /// values without a `Display` implementation be rendered too.
+    pub fn render<F, D>(&self, format: F) -> Render<'_, N, T, F>
+    where
+        F: Fn(&T) -> D,
+        D: Display,
+    {
+        Render {
+            table: self,
+            format,
+            viewport: (self.row_min, self.col_min)..=(self.row_max, self.col_max),
+            labels: true,
+            ticks: 5,
+            overlays: HashMap::new(),
+        }
+    }
+}
+
+impl<'a, N: Integer + Hash + Copy, T: Clone, F> Render<'a, N, T, F> {
+    /// Only renders the rectangle spanned by two corners, which may reach past the
+    /// bounds of the table.
+    pub fn viewport(self, viewport: RangeInclusive<(N, N)>) -> Self {
+        Render { viewport, ..self }
+    }
+
+    /// Whether to render the column ruler and row numbers.
+    pub fn labels(self, labels: bool) -> Self {
+        Render { labels, ..self }
+    }
+
+    /// Labels every column that is a multiple of `ticks` on the ruler, besides the first
+    /// and the last. With `0`, only the first and the last are labeled.
+    pub fn ticks(self, ticks: usize) -> Self {
+        Render { ticks, ..self }
+    }
+
+    /// Renders `marker` instead of the cell at `(row, col)`, without changing the table.
+    pub fn overlay(mut self, (row, col): (N, N), marker: impl Display) -> Self {
+        self.overlays.insert((row, col), marker.to_string());
+        self
+    }
+}
+
+impl<N, T, F, D> Display for Render<'_, N, T, F>
 where
     N: Integer + Hash + ToString + Display + Clone + ToPrimitive + Copy,
-    T: Clone + Display,
+    T: Clone,
+    F: Fn(&T) -> D,
+    D: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let row_min_num_width = self.row_min.to_string().len();
-        let row_max_num_width = self.row_max.to_string().len();
+        let (row_min, col_min) = *self.viewport.start();
+        let (row_max, col_max) = *self.viewport.end();
+        if row_min > row_max || col_min > col_max {
+            return Ok(());
+        }
+
+        let row_min_num_width = row_min.to_string().len();
+        let row_max_num_width = row_max.to_string().len();
         let row_num_width = row_min_num_width.max(row_max_num_width);
         // header
-        let col_max_num_length: u32 = self.col_max.to_string().len().to_u32().unwrap();
-        let col_min_num_length: u32 = self.col_min.to_string().len().to_u32().unwrap();
-        let col_num_length: u32 = col_min_num_length.max(col_max_num_length);
-        let row_start = self.col_min.to_isize().unwrap();
-        let row_stop = self.col_max.to_isize().unwrap();
-        for col_digit in (0..col_num_length).rev() {
-            for _ in 0..=row_num_width {
-                write!(f, " ")?
-            }
-            let magnitude = 10isize.pow(col_digit);
-            for i in range_inclusive(row_start, row_stop) {
-                if i == row_start || i == row_stop || i % 5 == Zero::zero() {
-                    if i.abs() >= magnitude || (i == 0 && magnitude == 1) {
-                        write!(f, "{}", (i / magnitude).to_string().chars().last().unwrap())?
+        if self.labels {
+            let col_max_num_length: u32 = col_max.to_string().len().to_u32().unwrap();
+            let col_min_num_length: u32 = col_min.to_string().len().to_u32().unwrap();
+            let col_num_length: u32 = col_min_num_length.max(col_max_num_length);
+            let row_start = col_min.to_isize().unwrap();
+            let row_stop = col_max.to_isize().unwrap();
+            let ticks = self.ticks.to_isize().unwrap();
+            for col_digit in (0..col_num_length).rev() {
+                for _ in 0..=row_num_width {
+                    write!(f, " ")?
+                }
+                let magnitude = 10isize.pow(col_digit);
+                for i in range_inclusive(row_start, row_stop) {
+                    if i == row_start || i == row_stop || (ticks > 0 && i % ticks == Zero::zero()) {
+                        if i.abs() >= magnitude || (i == 0 && magnitude == 1) {
+                            write!(f, "{}", (i / magnitude).to_string().chars().last().unwrap())?
+                        } else {
+                            write!(f, " ")?
+                        }
                     } else {
                         write!(f, " ")?
                     }
-                } else {
-                    write!(f, " ")?
                 }
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
 
-        // main body
-        let mut data_iter = self.data.iter();
-        let mut cell = data_iter.next();
-        for r in range_inclusive(self.row_min, self.row_max) {
-            write!(f, "{r:row_num_width$} ")?;
-            for c in range_inclusive(self.col_min, self.col_max) {
-                let res = match cell {
-                    Some((k, v)) => match (r, c).cmp(k) {
-                        Less => write!(f, "{}", self.get_default_for_row(r)),
-                        Equal => {
-                            let result = write!(f, "{v}");
-                            cell = data_iter.next();
-                            result
-                        }
-                        Greater => panic!("Something went wrong!"),
-                    },
-                    None => write!(f, "{}", self.get_default_for_row(r)),
+        // main body, walking the stored cells of the viewport in the same order as the loop.
+        let table = self.table;
+        let mut stored = table.range(self.viewport.clone()).peekable();
+        for r in range_inclusive(row_min, row_max) {
+            if self.labels {
+                write!(f, "{r:row_num_width$} ")?;
+            }
+            for c in range_inclusive(col_min, col_max) {
+                let value = match stored.next_if(|&(cell, _)| cell == (r, c)) {
+                    Some((_, value)) => Cow::Borrowed(value),
+                    None => table.resolve_default((r, c)),
                 };
-                res?
+                match self.overlays.get(&(r, c)) {
+                    Some(marker) => write!(f, "{marker}")?,
+                    None => write!(f, "{}", (self.format)(&value))?,
+                }
             }
             writeln!(f)?
         }
@@ -149,6 +400,16 @@ where
     }
 }
 
+impl<N, T> Display for SparseTable<N, T>
+where
+    N: Integer + Hash + ToString + Display + Clone + ToPrimitive + Copy,
+    T: Clone + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|value: &T| value.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -202,6 +463,114 @@ mod tests {
         assert_eq!(*table.col_max(), 6);
     }
 
+    #[test]
+    fn test_sparse_table_iter() {
+        let mut table: SparseTable<i32, char> = SparseTable::new('.');
+        table.insert((2, 1), 'c');
+        table.insert((0, 3), 'b');
+        table.insert((0, -1), 'a');
+        table.insert((2, 3), 'd');
+
+        assert_eq!(table.len(), 4);
+        assert!(!table.is_empty());
+        assert!(table.contains((0, 3)));
+        assert!(!table.contains((1, 3)));
+
+        let values = |cells: Vec<((i32, i32), &char)>| -> String {
+            cells.into_iter().map(|(_, value)| value).collect()
+        };
+        assert_eq!(values(table.iter().collect()), "abcd");
+        assert_eq!(table.iter().next(), Some(((0, -1), &'a')));
+        assert_eq!(values(table.iter_row(2).collect()), "cd");
+        assert_eq!(values(table.iter_row(1).collect()), "");
+        assert_eq!(values(table.iter_col(3).collect()), "bd");
+
+        assert_eq!(values(table.range((0, 0)..=(2, 3)).collect()), "bcd");
+        assert_eq!(values(table.range((0, 1)..=(2, 2)).collect()), "c");
+        assert_eq!(values(table.range((0, -1)..=(0, -1)).collect()), "a");
+        assert_eq!(values(table.range((2, 0)..=(0, 3)).collect()), "");
+    }
+
+    #[test]
+    fn test_sparse_table_remove() {
+        let mut table: SparseTable<i32, i32> = SparseTable::new_with_start_point(0, (1, 1));
+        table.insert((3, 5), 7);
+        table.insert((-2, 2), 2);
+        table.insert((2, -4), 3);
+        table.add_row_default(6, 9);
+
+        assert_eq!(table.remove((0, 0)), None);
+        assert_eq!(table.remove((3, 5)), Some(7));
+        assert_eq!(*table.get((3, 5)), 0);
+        assert_eq!(table.len(), 2);
+        assert_eq!(*table.col_max(), 2);
+        assert_eq!(*table.row_max(), 6);
+
+        table.remove((-2, 2));
+        table.remove((2, -4));
+        assert!(table.is_empty());
+        assert_eq!(*table.row_min(), 1);
+        assert_eq!(*table.row_max(), 6);
+        assert_eq!(*table.col_min(), 1);
+        assert_eq!(*table.col_max(), 1);
+    }
+
+    #[test]
+    fn test_sparse_table_default_rules() {
+        let mut table: SparseTable<i32, char> = SparseTable::new('.');
+        table.add_col_default(-1, '|');
+        table.add_row_default(4, '_');
+        assert_eq!(*table.get((2, -1)), '|');
+        assert_eq!(*table.get((4, 2)), '_');
+        // row defaults take precedence over column defaults.
+        assert_eq!(*table.get((4, -1)), '_');
+        assert_eq!(*table.col_min(), -1);
+        assert_eq!(*table.row_max(), 4);
+
+        table.add_default_rule(|(row, _)| (row >= 6).then_some('#'));
+        table.add_rectangle_default((1, 1)..=(2, 3), 'o');
+        assert_eq!(*table.resolve((1000, 1000)), '#');
+        assert!(matches!(table.resolve((1000, 1000)), Cow::Owned(_)));
+        // `get` only applies constant defaults.
+        assert_eq!(*table.get((1000, 1000)), '.');
+        assert_eq!(*table.get((2, 3)), 'o');
+        assert_eq!(*table.get((3, 3)), '.');
+        assert_eq!(*table.get((4, 2)), '_');
+        assert_eq!(*table.row_max(), 4);
+
+        // later rules take precedence over earlier ones, and stored cells over any rule.
+        table.add_rectangle_default((5, 0)..=(6, 1), '~');
+        assert_eq!(*table.get((6, 0)), '~');
+        assert_eq!(*table.resolve((6, 2)), '#');
+        table.insert((6, 0), 'x');
+        assert_eq!(*table.get((6, 0)), 'x');
+
+        table.remove((6, 0));
+        assert_eq!(*table.row_max(), 6);
+        assert_eq!(*table.col_min(), -1);
+        assert_eq!(*table.col_max(), 3);
+
+        let rendered = format!("{table}");
+        let body: Vec<&str> = rendered.lines().skip(2).collect();
+        assert_eq!(
+            body.join("\n"),
+            indoc! {"
+             0 |....
+             1 |.ooo
+             2 |.ooo
+             3 |....
+             4 _____
+             5 |~~..
+             6 #~~##"}
+        );
+    }
+
+    #[test]
+    fn test_sparse_table_is_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<SparseTable<i32, char>>();
+    }
+
     #[test]
     fn test_sparse_table_display() {
         let table: SparseTable<i32, i32> = SparseTable::new(0);
@@ -293,6 +662,58 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_sparse_table_render() {
+        #[derive(Clone, PartialEq)]
+        enum Tile {
+            Air,
+            Rock,
+        }
+
+        let mut table = SparseTable::new_with_start_point(Tile::Air, (0, 500));
+        for col in 494..=503 {
+            table.insert((9, col), Tile::Rock);
+        }
+        table.insert((4, 498), Tile::Rock);
+        let format = |tile: &Tile| match tile {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+        };
+
+        assert_eq!(
+            table
+                .render(format)
+                .viewport((3, 497)..=(5, 502))
+                .ticks(2)
+                .overlay((3, 500), '+')
+                .to_string(),
+            indoc! {"
+                  44 5 5
+                  99 0 0
+                  78 0 2
+                3 ...+..
+                4 .#....
+                5 ......
+            "}
+        );
+        assert_eq!(
+            table
+                .render(format)
+                .viewport((8, 493)..=(10, 495))
+                .labels(false)
+                .to_string(),
+            indoc! {"
+                ...
+                .##
+                ...
+            "}
+        );
+        assert_eq!(
+            table.render(format).viewport((2, 0)..=(1, 0)).to_string(),
+            ""
+        );
+    }
+
     #[test]
     fn test_sparse_table_with_row_default() {
         let mut table: SparseTable<i32, i32> = SparseTable::new(0);
@@ -0,0 +1,336 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::puzzle;
+
+/// Wrong answers of every day of a year, so they are never submitted twice.
+pub fn attempts_path(year: i16) -> PathBuf {
+    crate::year_path("answers", year).join("attempts.csv")
+}
+
+const HEADER: &str = "day,part,rejection,answer";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Rejection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rejection::TooHigh => "too_high",
+            Rejection::TooLow => "too_low",
+            Rejection::Wrong => "wrong",
+        }
+    }
+}
+
+/// How the server responded to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// The answer is wrong. Another one may only be submitted after `retry_after`.
+    Rejected {
+        rejection: Rejection,
+        retry_after: Option<Duration>,
+    },
+    /// An answer was submitted too recently, nothing was checked.
+    TooRecent(Duration),
+    /// The part is already solved, or still locked.
+    WrongLevel,
+    /// Anything else, with the text of the response.
+    Unknown(String),
+}
+
+lazy_static! {
+    static ref TIME_LEFT: Regex =
+        Regex::new(r"[Yy]ou have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref WAIT: Regex =
+        Regex::new(r"[Pp]lease wait (one|\d+) minutes? before trying again").unwrap();
+}
+
+fn retry_after(text: &str) -> Option<Duration> {
+    if let Some(captures) = TIME_LEFT.captures(text) {
+        let number = |i| {
+            captures
+                .get(i)
+                .and_then(|m| m.as_str().parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+        return Some(Duration::from_secs(number(1) * 60 + number(2)));
+    }
+    WAIT.captures(text).map(|captures| {
+        let minutes = match &captures[1] {
+            "one" => 1,
+            minutes => minutes.parse().unwrap_or(1),
+        };
+        Duration::from_secs(minutes * 60)
+    })
+}
+
+/// Reads the outcome from the response page of a submitted answer.
+pub fn parse_outcome(html: &str) -> Outcome {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+    let text = puzzle::text(&html[start..end]);
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let rejected = |rejection| Outcome::Rejected {
+        rejection,
+        retry_after: retry_after(&text),
+    };
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("your answer is too high") {
+        rejected(Rejection::TooHigh)
+    } else if text.contains("your answer is too low") {
+        rejected(Rejection::TooLow)
+    } else if text.contains("That's not the right answer") {
+        rejected(Rejection::Wrong)
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::TooRecent(retry_after(&text).unwrap_or(Duration::from_secs(60)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub rejection: Rejection,
+    pub answer: String,
+}
+
+/// Quotes a field if it holds a comma, a quote or a line break, doubling its quotes.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits CSV into records of fields. Quoted fields may span several lines.
+fn parse_records(input: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.next_if_eq(&'"').is_some() => field.push('"'),
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+pub fn parse_attempts(input: &str) -> Vec<Attempt> {
+    parse_records(input)
+        .into_iter()
+        .filter(|record| record.join(",") != HEADER)
+        .filter_map(|record| {
+            let [day, part, rejection, answer] = &record[..] else {
+                return None;
+            };
+            let rejection = match rejection.as_str() {
+                "too_high" => Rejection::TooHigh,
+                "too_low" => Rejection::TooLow,
+                "wrong" => Rejection::Wrong,
+                _ => return None,
+            };
+            Some(Attempt {
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                rejection,
+                answer: answer.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn load_attempts(year: i16) -> io::Result<Vec<Attempt>> {
+    match fs::read_to_string(attempts_path(year)) {
+        Ok(contents) => Ok(parse_attempts(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn record_attempt(year: i16, attempt: &Attempt) -> io::Result<()> {
+    fs::create_dir_all(crate::year_path("answers", year))?;
+    let path = attempts_path(year);
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+    writeln!(
+        file,
+        "{},{},{},{}",
+        attempt.day,
+        attempt.part,
+        attempt.rejection.name(),
+        quote(&attempt.answer)
+    )
+}
+
+/// Refuses answers that were rejected before, or that lie outside the bounds given by
+/// earlier "too high" and "too low" answers.
+pub fn guard(attempts: &[Attempt], day: u8, part: u8, answer: &str) -> Result<(), String> {
+    let attempts: Vec<&Attempt> = attempts
+        .iter()
+        .filter(|a| a.day == day && a.part == part)
+        .collect();
+
+    if attempts.iter().any(|a| a.answer == answer) {
+        return Err(format!("{answer} was already rejected."));
+    }
+
+    let Ok(value) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+    let bound = |rejection| {
+        attempts
+            .iter()
+            .filter(move |a| a.rejection == rejection)
+            .filter_map(|a| a.answer.parse::<i128>().ok())
+    };
+    if let Some(too_high) = bound(Rejection::TooHigh)
+        .filter(|&high| value >= high)
+        .min()
+    {
+        return Err(format!("{answer} is too high, {too_high} already was."));
+    }
+    if let Some(too_low) = bound(Rejection::TooLow).filter(|&low| value <= low).max() {
+        return Err(format!("{answer} is too low, {too_low} already was."));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(
+            parse_outcome(&page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.")),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_outcome(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>")),
+            Outcome::Rejected {
+                rejection: Rejection::TooHigh,
+                retry_after: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_outcome(&page(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            )),
+            Outcome::Rejected {
+                rejection: Rejection::Wrong,
+                retry_after: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_outcome(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.")),
+            Outcome::TooRecent(Duration::from_secs(272))
+        );
+        assert_eq!(
+            parse_outcome(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Outcome::WrongLevel
+        );
+    }
+
+    const ATTEMPTS: &str = indoc! {"
+        day,part,rejection,answer
+        1,1,too_high,5000
+        1,1,too_low,100
+        1,1,too_high,7000
+        1,1,wrong,abc
+        1,2,too_low,9000
+    "};
+
+    #[test]
+    fn test_parse_attempts() {
+        let attempts = parse_attempts(ATTEMPTS);
+        assert_eq!(attempts.len(), 5);
+        assert_eq!(
+            attempts[1],
+            Attempt {
+                day: 1,
+                part: 1,
+                rejection: Rejection::TooLow,
+                answer: String::from("100")
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_attempts() {
+        let answers = ["#..#\n.##.", "1,2", "say \"hi\"", "plain"];
+        let mut csv = format!("{HEADER}\r\n");
+        for answer in answers {
+            csv.push_str(&format!("10,2,wrong,{}\n", quote(answer)));
+        }
+        let parsed: Vec<String> = parse_attempts(&csv)
+            .into_iter()
+            .map(|attempt| attempt.answer)
+            .collect();
+        assert_eq!(parsed, answers);
+
+        assert!(parse_attempts("3,1,wrong\n").is_empty());
+    }
+
+    #[test]
+    fn test_guard() {
+        let attempts = parse_attempts(ATTEMPTS);
+        assert_eq!(guard(&attempts, 1, 1, "2500"), Ok(()));
+        assert!(guard(&attempts, 1, 1, "abc").is_err());
+        assert!(guard(&attempts, 1, 1, "5000").is_err());
+        assert!(guard(&attempts, 1, 1, "6000").is_err());
+        assert!(guard(&attempts, 1, 1, "99").is_err());
+        assert!(guard(&attempts, 1, 2, "6000").is_err());
+        assert_eq!(guard(&attempts, 2, 1, "6000"), Ok(()));
+    }
+}
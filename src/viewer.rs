@@ -0,0 +1,309 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::ANSI_BOLD;
+use crate::ANSI_RESET;
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// How many states `c` pulls at most before giving up on finding a pause.
+const LOOK_AHEAD: usize = 10_000;
+
+const HELP: &str = "[enter] next, b back, +N/-N jump N steps, g N go to step N, c continue to the next pause, q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Step(isize),
+    GoTo(usize),
+    Continue,
+    Help,
+    Quit,
+}
+
+fn number<T: FromStr>(n: &str) -> Result<T, String> {
+    let n = n.trim();
+    n.parse()
+        .map_err(|_| format!("expected a number, got \"{n}\""))
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let line = line.trim();
+    match line {
+        "" | "n" => Ok(Command::Step(1)),
+        "b" | "p" => Ok(Command::Step(-1)),
+        "c" => Ok(Command::Continue),
+        "h" | "?" => Ok(Command::Help),
+        "q" => Ok(Command::Quit),
+        _ if line.starts_with('+') => Ok(Command::Step(number(&line[1..])?)),
+        _ if line.starts_with('-') => Ok(Command::Step(-number::<isize>(&line[1..])?)),
+        _ => match line.strip_prefix('g') {
+            Some(step) => Ok(Command::GoTo(number(step)?)),
+            None => Err(format!("unknown command \"{line}\"")),
+        },
+    }
+}
+
+type Predicate<'a, S> = Box<dyn Fn(&S) -> bool + 'a>;
+
+/// Steps through the states of a simulation in the terminal, one screen per state.
+/// States are pulled from the iterator only when needed and kept as text, so stepping
+/// back works for iterators of any length, including endless ones.
+///
+/// A day only needs to expose its states, e.g. within 2022 day 14:
+/// `Viewer::new(iter::from_fn(|| cave.drop_sand().map(|_| cave.to_string())))`.
+pub struct Viewer<'a, S> {
+    states: Box<dyn Iterator<Item = S> + 'a>,
+    pause_when: Option<Predicate<'a, S>>,
+    clear_screen: bool,
+    look_ahead: usize,
+    frames: Vec<String>,
+    pauses: Vec<bool>,
+    exhausted: bool,
+    current: usize,
+}
+
+impl<'a, S: Display> Viewer<'a, S> {
+    pub fn new(states: impl IntoIterator<Item = S> + 'a) -> Self {
+        Viewer {
+            states: Box::new(states.into_iter()),
+            pause_when: None,
+            clear_screen: true,
+            look_ahead: LOOK_AHEAD,
+            frames: vec![],
+            pauses: vec![],
+            exhausted: false,
+            current: 0,
+        }
+    }
+
+    /// Makes `c` stop at the next state matching `predicate`, instead of the last one.
+    pub fn pause_when(self, predicate: impl Fn(&S) -> bool + 'a) -> Self {
+        Viewer {
+            pause_when: Some(Box::new(predicate)),
+            ..self
+        }
+    }
+
+    /// Whether to clear the terminal before every state, which is the default.
+    pub fn clear_screen(self, clear_screen: bool) -> Self {
+        Viewer {
+            clear_screen,
+            ..self
+        }
+    }
+
+    /// How many states `c` goes ahead at most when no state matches `pause_when`, so it
+    /// does not run forever on an endless simulation. Defaults to 10,000.
+    pub fn look_ahead(self, look_ahead: usize) -> Self {
+        Viewer {
+            look_ahead: look_ahead.max(1),
+            ..self
+        }
+    }
+
+    /// Pulls states until `step` is known. Returns whether it exists.
+    fn load(&mut self, step: usize) -> bool {
+        while self.frames.len() <= step && !self.exhausted {
+            match self.states.next() {
+                Some(state) => {
+                    let pause = self.pause_when.as_ref().is_some_and(|p| p(&state));
+                    self.frames.push(state.to_string());
+                    self.pauses.push(pause);
+                }
+                None => self.exhausted = true,
+            }
+        }
+        step < self.frames.len()
+    }
+
+    /// Moves to `step`, or to the last state if there are fewer.
+    fn go_to(&mut self, step: usize) {
+        self.current = if self.load(step) {
+            step
+        } else {
+            self.frames.len().saturating_sub(1)
+        };
+    }
+
+    /// Moves to the next pause, or the last state if there is none. Stops after
+    /// `look_ahead` states and returns a message saying so.
+    fn continue_to_pause(&mut self) -> Option<String> {
+        let limit = self.current.saturating_add(self.look_ahead);
+        let mut step = self.current + 1;
+        while self.load(step) {
+            if self.pauses[step] {
+                break;
+            }
+            if step == limit {
+                self.go_to(step);
+                return Some(format!(
+                    "No pause within {} steps, stopped at step {step}.",
+                    self.look_ahead
+                ));
+            }
+            step += 1;
+        }
+        self.go_to(step);
+        None
+    }
+
+    fn show(&self, output: &mut impl Write, message: Option<&str>) -> io::Result<()> {
+        if self.clear_screen {
+            write!(output, "{CLEAR_SCREEN}")?;
+        }
+        let Some(frame) = self.frames.get(self.current) else {
+            return writeln!(output, "There are no states to show.");
+        };
+        write!(output, "{frame}")?;
+        if !frame.ends_with('\n') {
+            writeln!(output)?;
+        }
+        let known = if self.exhausted {
+            format!("{}", self.frames.len() - 1)
+        } else {
+            format!("{}+", self.frames.len() - 1)
+        };
+        let paused = if self.pauses[self.current] {
+            " (paused)"
+        } else {
+            ""
+        };
+        writeln!(
+            output,
+            "{ANSI_BOLD}Step {} of {known}{paused}{ANSI_RESET}",
+            self.current
+        )?;
+        if let Some(message) = message {
+            writeln!(output, "{message}")?;
+        }
+        write!(output, "> ")?;
+        output.flush()
+    }
+
+    /// Shows the states in the terminal until `q` is entered or stdin is closed.
+    pub fn run(&mut self) -> io::Result<()> {
+        self.run_with(io::stdin().lock(), io::stdout().lock())
+    }
+
+    /// Like [`Viewer::run`], reading commands from `input` and writing to `output`.
+    pub fn run_with(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        self.go_to(0);
+        self.show(&mut output, Some(HELP))?;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return writeln!(output);
+            }
+            let message = match parse_command(&line) {
+                Ok(Command::Step(n)) => {
+                    self.go_to(self.current.saturating_add_signed(n));
+                    None
+                }
+                Ok(Command::GoTo(step)) => {
+                    self.go_to(step);
+                    None
+                }
+                Ok(Command::Continue) => self.continue_to_pause(),
+                Ok(Command::Help) => Some(String::from(HELP)),
+                Ok(Command::Quit) => return Ok(()),
+                Err(e) => Some(format!("{e}, enter h for help")),
+            };
+            self.show(&mut output, message.as_deref())?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The steps shown while running `commands` on the states 0 to 9, pausing on
+    /// multiples of 4.
+    fn shown_steps(commands: &str) -> Vec<String> {
+        let mut output = vec![];
+        Viewer::new(0..10)
+            .pause_when(|n| n % 4 == 0)
+            .clear_screen(false)
+            .run_with(commands.as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.split("Step ").nth(1))
+            .map(|step| step.trim_end_matches(ANSI_RESET).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("\n"), Ok(Command::Step(1)));
+        assert_eq!(parse_command("b"), Ok(Command::Step(-1)));
+        assert_eq!(parse_command("+10"), Ok(Command::Step(10)));
+        assert_eq!(parse_command("-3"), Ok(Command::Step(-3)));
+        assert_eq!(parse_command("g 7"), Ok(Command::GoTo(7)));
+        assert_eq!(parse_command("c"), Ok(Command::Continue));
+        assert!(parse_command("+x").is_err());
+        assert!(parse_command("x").is_err());
+    }
+
+    #[test]
+    fn test_viewer() {
+        assert_eq!(
+            shown_steps("\n\nb\n+5\n-9\nc\nc\nc\nc\ng 2\nx\n"),
+            vec![
+                "0 of 0+ (paused)",
+                "1 of 1+",
+                "2 of 2+",
+                "1 of 2+",
+                "6 of 6+",
+                "0 of 6+ (paused)",
+                "4 of 6+ (paused)",
+                "8 of 8+ (paused)",
+                "9 of 9",
+                "9 of 9",
+                "2 of 9",
+                "2 of 9",
+            ]
+        );
+        assert_eq!(
+            shown_steps("g 100\nq\n+1\n"),
+            vec!["0 of 0+ (paused)", "9 of 9"]
+        );
+    }
+
+    #[test]
+    fn test_viewer_with_endless_states() {
+        let mut output = vec![];
+        let mut viewer = Viewer::new(0..)
+            .pause_when(|_| false)
+            .look_ahead(100)
+            .clear_screen(false);
+        viewer.run_with("c\nc\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Step 100 of 100+"));
+        assert!(output.contains("No pause within 100 steps, stopped at step 200."));
+        assert_eq!(viewer.frames.len(), 201);
+
+        let mut viewer = Viewer::new(0..).look_ahead(50).clear_screen(false);
+        viewer.run_with("c\n".as_bytes(), &mut vec![]).unwrap();
+        assert_eq!(viewer.current, 50);
+    }
+
+    #[test]
+    fn test_viewer_without_states() {
+        let mut output = vec![];
+        Viewer::new(Vec::<String>::new())
+            .clear_screen(false)
+            .run_with("\n".as_bytes(), &mut output)
+            .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .starts_with("There are no states to show."));
+    }
+}